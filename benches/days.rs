@@ -0,0 +1,125 @@
+//! Benchmarks `part_one` and `part_two` of every day listed below against its real input.
+//! `cargo scaffold` adds new days to the list, so solutions do not need a bench file of their own. Days that
+//! were added by hand are caught before benching, see [`check_listed_days`].
+use std::{fs, hint::black_box, path::Path};
+
+use advent_of_code::template::Day;
+use criterion::{Criterion, criterion_group, criterion_main};
+
+/// A solution part that can be benched.
+trait Part {
+    fn bench(&self, c: &mut Criterion, name: &str, input: &str);
+}
+
+impl<F: Fn(&str) -> T, T> Part for F {
+    fn bench(&self, c: &mut Criterion, name: &str, input: &str) {
+        c.bench_function(name, |b| {
+            b.iter(|| self(black_box(input)));
+        });
+    }
+}
+
+/// Stands in for a part that a day does not define. Unused while every day defines both parts.
+#[allow(dead_code)]
+struct Missing;
+
+impl Part for Missing {
+    fn bench(&self, _: &mut Criterion, _: &str, _: &str) {}
+}
+
+/// `part_two` of days without one, e.g. day 25. `benchmark_days!` glob-imports each day in an inner scope,
+/// so a `part_two` defined by the day shadows this one.
+#[allow(dead_code, non_upper_case_globals)]
+const part_two: Missing = Missing;
+
+fn bench_part(c: &mut Criterion, day: Day, part: u8, input: &str, func: impl Part) {
+    func.bench(c, &format!("Day {day}/Part {part}"), input);
+}
+
+fn read_input(day: Day) -> Option<String> {
+    let path = Path::new("data").join("inputs").join(format!("{day}.txt"));
+    fs::read_to_string(path).ok()
+}
+
+/// Panic if the days listed in `benchmark_days!` differ from the solutions in `src/bin`, e.g. because a day
+/// was added without `cargo scaffold`.
+fn check_listed_days(listed: &[u8]) {
+    let mut solved: Vec<Day> = fs::read_dir(Path::new("src").join("bin"))
+        .expect("failed to read `src/bin`")
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "rs" {
+                return None;
+            }
+            path.file_stem()?.to_str()?.parse().ok()
+        })
+        .collect();
+    solved.sort_unstable();
+
+    let listed: Vec<Day> = listed.iter().filter_map(|day| Day::new(*day)).collect();
+    let missing: Vec<String> = solved
+        .iter()
+        .filter(|day| !listed.contains(day))
+        .map(|day| {
+            format!(
+                "    ({}, day_{day}, \"../src/bin/{day}.rs\"),",
+                day.into_inner()
+            )
+        })
+        .collect();
+    let stale: Vec<String> = listed
+        .iter()
+        .filter(|day| !solved.contains(day))
+        .map(|day| format!("src/bin/{day}.rs"))
+        .collect();
+
+    assert!(
+        missing.is_empty(),
+        "`benches/days.rs` does not list every day in `src/bin`. Add these lines to `benchmark_days!`:\n{}",
+        missing.join("\n")
+    );
+    assert!(
+        stale.is_empty(),
+        "`benches/days.rs` lists days that do not exist: {}",
+        stale.join(", ")
+    );
+}
+
+macro_rules! benchmark_days {
+    ($( ($day:literal, $module:ident, $path:literal) ),* $(,)?) => {
+        $(
+            #[path = $path]
+            #[allow(dead_code, unused, clippy::all, clippy::pedantic)]
+            mod $module;
+        )*
+
+        fn bench_days(c: &mut Criterion) {
+            check_listed_days(&[$($day),*]);
+
+            $(
+                let day = advent_of_code::day!($day);
+                match read_input(day) {
+                    Some(input) => {
+                        #[allow(unused_imports)]
+                        use $module::*;
+
+                        crate::bench_part(c, day, 1, &input, part_one);
+                        crate::bench_part(c, day, 2, &input, part_two);
+                    }
+                    None => eprintln!("Skipping day {day}: no input in `data/inputs/{day}.txt`."),
+                }
+            )*
+        }
+    };
+}
+
+benchmark_days! {
+    (1, day_01, "../src/bin/01.rs"),
+    (2, day_02, "../src/bin/02.rs"),
+    (3, day_03, "../src/bin/03.rs"),
+    (4, day_04, "../src/bin/04.rs"),
+    (5, day_05, "../src/bin/05.rs"),
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
@@ -0,0 +1,219 @@
+//! Derive macros of the `advent_of_code` library crate.
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Lifetime, LitStr, Type, parse_macro_input};
+
+/// Implements `FromPattern` and `HasPattern` for a struct with named fields, using the line pattern given in
+/// the `#[pattern("...")]` attribute. Every field has to be named by exactly one placeholder.
+/// Structs without lifetime parameters also implement `FromStr`.
+#[proc_macro_derive(FromPattern, attributes(pattern))]
+pub fn derive_from_pattern(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A placeholder of a pattern, as `(name, type)`.
+type Placeholder = (String, Option<String>);
+
+/// Extract the placeholders of `pattern`. Mirrors `Pattern::new` of the library, which reports malformed
+/// patterns at runtime, so that they fail to compile instead.
+fn placeholders(pattern: &str) -> Result<Vec<Placeholder>, String> {
+    let mut placeholders = vec![];
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') | None => return Err("unclosed placeholder".into()),
+                        Some(c) => placeholder.push(c),
+                    }
+                }
+
+                let (name, type_name) = match placeholder.split_once(':') {
+                    Some((name, type_name)) => (name.trim(), Some(type_name.trim())),
+                    None => (placeholder.trim(), None),
+                };
+
+                if name.is_empty() {
+                    return Err(
+                        "placeholders of derived patterns need a field name, e.g. `{steps}`".into(),
+                    );
+                }
+
+                placeholders.push((
+                    name.to_string(),
+                    type_name.filter(|x| !x.is_empty()).map(String::from),
+                ));
+            }
+            '}' => return Err("unmatched `}`, use `}}` for a literal brace".into()),
+            _ => {}
+        }
+    }
+
+    Ok(placeholders)
+}
+
+/// Name of `ty` as written in placeholders, e.g. `str` for `&'a str` and `u64` for `std::primitive::u64`.
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Reference(reference) => type_name(&reference.elem),
+        Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let attribute = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("pattern"))
+        .ok_or_else(|| syn::Error::new_spanned(name, "missing `#[pattern(\"...\")]` attribute"))?;
+    let pattern: LitStr = attribute.parse_args()?;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "`FromPattern` can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "`FromPattern` can only be derived for structs with named fields",
+        ));
+    };
+
+    if input.generics.type_params().next().is_some()
+        || input.generics.const_params().next().is_some()
+    {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`FromPattern` only supports lifetime parameters",
+        ));
+    }
+
+    let placeholders =
+        placeholders(&pattern.value()).map_err(|e| syn::Error::new_spanned(&pattern, e))?;
+
+    let mut field_types = vec![];
+    let mut initializers = vec![];
+
+    for (index, (placeholder, expected)) in placeholders.iter().enumerate() {
+        let field = fields
+            .named
+            .iter()
+            .find(|f| f.ident.as_ref().is_some_and(|ident| ident == placeholder))
+            .ok_or_else(|| {
+                syn::Error::new_spanned(&pattern, format!("`{name}` has no field `{placeholder}`"))
+            })?;
+
+        if placeholders[..index]
+            .iter()
+            .any(|(other, _)| other == placeholder)
+        {
+            return Err(syn::Error::new_spanned(
+                &pattern,
+                format!("field `{placeholder}` is used more than once"),
+            ));
+        }
+
+        if let Some(expected) = expected
+            && type_name(&field.ty).is_some_and(|actual| &actual != expected)
+        {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                format!("the pattern expects `{placeholder}` to be a `{expected}`"),
+            ));
+        }
+
+        let ident = &field.ident;
+        let ty = &field.ty;
+        field_types.push(ty);
+        initializers.push(quote! { #ident: <#ty as __Field>::parse_field(fields[#index])? });
+    }
+
+    if let Some(field) = fields.named.iter().find(|f| {
+        !placeholders
+            .iter()
+            .any(|(placeholder, _)| f.ident.as_ref().is_some_and(|ident| ident == placeholder))
+    }) {
+        return Err(syn::Error::new_spanned(
+            field,
+            "field is not named by a placeholder of the pattern",
+        ));
+    }
+
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let lifetimes: Vec<&Lifetime> = input.generics.lifetimes().map(|l| &l.lifetime).collect();
+
+    // structs without a lifetime can be parsed from input of any lifetime.
+    let (lifetime, impl_generics) = match lifetimes.as_slice() {
+        [] => {
+            let lifetime = Lifetime::new("'__input", Span::call_site());
+            (lifetime.clone(), quote! { <#lifetime> })
+        }
+        [lifetime] => ((*lifetime).clone(), quote! { <#lifetime> }),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.generics,
+                "`FromPattern` supports at most one lifetime parameter",
+            ));
+        }
+    };
+
+    let from_str = lifetimes.is_empty().then(|| {
+        quote! {
+            impl ::std::str::FromStr for #name {
+                type Err = ::advent_of_code::parse::ParseError;
+
+                fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                    <Self as ::advent_of_code::parse::pattern::HasPattern>::parse_line(
+                        ::advent_of_code::parse::Line::new(s, 1, 1),
+                    )
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        const _: () = {
+            use ::advent_of_code::parse::pattern::{Extent, Field as __Field, FromPattern, HasPattern, Pattern};
+            use ::advent_of_code::parse::{Line, ParseError};
+
+            impl #impl_generics FromPattern<#lifetime> for #name #ty_generics #where_clause {
+                const FIELDS: &'static [(Extent, &'static str)] = &[
+                    #((<#field_types as __Field<#lifetime>>::EXTENT, <#field_types as __Field<#lifetime>>::TYPE_NAME)),*
+                ];
+
+                fn from_fields(fields: &[Line<#lifetime>]) -> ::std::result::Result<Self, ParseError> {
+                    ::std::result::Result::Ok(Self { #(#initializers),* })
+                }
+            }
+
+            impl #impl_generics HasPattern<#lifetime> for #name #ty_generics #where_clause {
+                fn pattern() -> &'static Pattern {
+                    static PATTERN: ::std::sync::LazyLock<Pattern> =
+                        ::std::sync::LazyLock::new(|| Pattern::new(#pattern).unwrap());
+                    &PATTERN
+                }
+            }
+
+            #from_str
+        };
+    })
+}
@@ -0,0 +1,391 @@
+//! Cellular automata on a [`Grid`], where every cell is updated from its own value and its neighbours.
+//!
+//! Rules are functions from a [`Cell`] to the next value of that cell. They can be applied
+//! - synchronously with [`Automaton::step`], where every cell sees the previous generation,
+//! - in place with [`Automaton::step_in_place`], where cells see the updates of cells before them,
+//! - until nothing changes with [`Automaton::run_until_stable`] and [`Automaton::propagate`], which only
+//!   revisit the neighbours of changed cells,
+//! - for many generations with [`Automaton::run_for`], which skips ahead once the grid repeats.
+//!
+//! ```
+//! use advent_of_code::automaton::{Automaton, Neighbourhood};
+//! use grid::grid;
+//!
+//! // remove every `1` with fewer than two `1` neighbours, until none is left to remove.
+//! let mut automaton = Automaton::new(grid![[1, 1, 0][0, 1, 0][0, 0, 1]], Neighbourhood::Moore);
+//! let removed = automaton.propagate(|cell| {
+//!     if *cell.value() == 1 && cell.count(|x| *x == 1) < 2 { 0 } else { *cell.value() }
+//! });
+//! assert_eq!(removed, 1);
+//! ```
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use grid::Grid;
+
+pub use crate::geometry::{Neighbourhood, Position};
+
+/// A cell as seen by a rule.
+pub struct Cell<'a, T> {
+    grid: &'a Grid<T>,
+    position: Position,
+    neighbourhood: Neighbourhood,
+}
+
+impl<'a, T> Cell<'a, T> {
+    pub fn value(&self) -> &'a T {
+        &self.grid[self.position]
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Values of the neighbours within the grid. Cells on the edge have fewer neighbours.
+    pub fn neighbours(&self) -> impl Iterator<Item = &'a T> + use<'a, T> {
+        let grid = self.grid;
+        self.neighbourhood
+            .positions(self.position, grid.rows(), grid.cols())
+            .map(move |position| &grid[position])
+    }
+
+    /// Number of neighbours matching `predicate`.
+    pub fn count(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.neighbours().filter(|x| predicate(x)).count()
+    }
+}
+
+/// A cycle of generations: the grid of generation `start + length` equals the one of `start`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest generation with the same grid as `generation`.
+    pub fn equivalent(&self, generation: usize) -> usize {
+        if generation < self.start {
+            generation
+        } else {
+            self.start + (generation - self.start) % self.length
+        }
+    }
+}
+
+/// A grid that evolves by rules.
+#[derive(Clone, Debug)]
+pub struct Automaton<T> {
+    grid: Grid<T>,
+    neighbourhood: Neighbourhood,
+    generation: usize,
+}
+
+impl<T: Clone + PartialEq> Automaton<T> {
+    pub fn new(grid: Grid<T>, neighbourhood: Neighbourhood) -> Self {
+        Automaton {
+            grid,
+            neighbourhood,
+            generation: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Number of generations computed so far. Only [`Automaton::propagate`] does not count generations, as
+    /// its updates don't form them.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    fn cell(&self, position: Position) -> Cell<'_, T> {
+        Cell {
+            grid: &self.grid,
+            position,
+            neighbourhood: self.neighbourhood,
+        }
+    }
+
+    fn positions(&self) -> impl Iterator<Item = Position> + use<T> {
+        let cols = self.grid.cols();
+        (0..self.grid.rows()).flat_map(move |row| (0..cols).map(move |col| (row, col)))
+    }
+
+    fn index(&self, (row, col): Position) -> usize {
+        row * self.grid.cols() + col
+    }
+
+    /// Synchronously update `positions`, returning the ones that changed.
+    fn update(
+        &mut self,
+        positions: impl Iterator<Item = Position>,
+        rule: &impl Fn(Cell<'_, T>) -> T,
+    ) -> Vec<Position> {
+        let updates: Vec<(Position, T)> = positions
+            .filter_map(|position| {
+                let next = rule(self.cell(position));
+                (next != self.grid[position]).then_some((position, next))
+            })
+            .collect();
+
+        updates
+            .into_iter()
+            .map(|(position, next)| {
+                self.grid[position] = next;
+                position
+            })
+            .collect()
+    }
+
+    /// Changed positions and their neighbours, without duplicates.
+    fn affected(&self, changed: &[Position]) -> Vec<Position> {
+        let mut seen = vec![false; self.grid.rows() * self.grid.cols()];
+        let mut affected = vec![];
+
+        for &position in changed {
+            let neighbours =
+                self.neighbourhood
+                    .positions(position, self.grid.rows(), self.grid.cols());
+            for position in std::iter::once(position).chain(neighbours) {
+                let index = self.index(position);
+                if !seen[index] {
+                    seen[index] = true;
+                    affected.push(position);
+                }
+            }
+        }
+
+        affected
+    }
+
+    /// Compute the next generation, where every cell is updated from the current one. Returns the number of
+    /// changed cells.
+    pub fn step(&mut self, rule: impl Fn(Cell<'_, T>) -> T) -> usize {
+        self.generation += 1;
+        self.update(self.positions(), &rule).len()
+    }
+
+    /// Compute the next generation by updating cells in row-major order, so that a cell already sees the new
+    /// values of the cells before it. Returns the number of changed cells.
+    pub fn step_in_place(&mut self, rule: impl Fn(Cell<'_, T>) -> T) -> usize {
+        self.generation += 1;
+
+        let mut changed = 0;
+        for position in self.positions() {
+            let next = rule(self.cell(position));
+            if next != self.grid[position] {
+                self.grid[position] = next;
+                changed += 1;
+            }
+        }
+
+        changed
+    }
+
+    /// Compute synchronous generations until one doesn't change anything. After the first generation, only
+    /// changed cells and their neighbours are updated. Returns the number of generations that changed the
+    /// grid, which are the ones counted.
+    pub fn run_until_stable(&mut self, rule: impl Fn(Cell<'_, T>) -> T) -> usize {
+        let mut generations = 0;
+        let mut changed = self.update(self.positions(), &rule);
+
+        while !changed.is_empty() {
+            generations += 1;
+            let affected = self.affected(&changed);
+            changed = self.update(affected.into_iter(), &rule);
+        }
+
+        self.generation += generations;
+        generations
+    }
+
+    /// Update cells in place from a worklist until the rule doesn't change any cell anymore. A changed cell
+    /// queues its neighbours, so that only those are revisited. Returns the number of updates.
+    ///
+    /// The result is only independent of the order of updates for rules that can't undo their own changes,
+    /// e.g. cells that are removed and never come back.
+    pub fn propagate(&mut self, rule: impl Fn(Cell<'_, T>) -> T) -> usize {
+        let mut queued = vec![true; self.grid.rows() * self.grid.cols()];
+        let mut worklist: Vec<Position> = self.positions().collect();
+        worklist.reverse();
+
+        let mut updates = 0;
+        while let Some(position) = worklist.pop() {
+            let index = self.index(position);
+            queued[index] = false;
+
+            let next = rule(self.cell(position));
+            if next == self.grid[position] {
+                continue;
+            }
+
+            self.grid[position] = next;
+            updates += 1;
+
+            for neighbour in
+                self.neighbourhood
+                    .positions(position, self.grid.rows(), self.grid.cols())
+            {
+                let index = self.index(neighbour);
+                if !queued[index] {
+                    queued[index] = true;
+                    worklist.push(neighbour);
+                }
+            }
+        }
+
+        updates
+    }
+}
+
+impl<T: Clone + Eq + Hash> Automaton<T> {
+    /// Compute synchronous generations until a grid repeats, at most `limit` of them. A stable grid is a
+    /// cycle of length `1`.
+    pub fn find_cycle(&mut self, rule: impl Fn(Cell<'_, T>) -> T, limit: usize) -> Option<Cycle> {
+        let mut seen = HashMap::from([(self.grid.clone(), self.generation)]);
+
+        for _ in 0..limit {
+            self.step(&rule);
+            if let Some(start) = seen.insert(self.grid.clone(), self.generation) {
+                return Some(Cycle {
+                    start,
+                    length: self.generation - start,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Compute `generations` synchronous generations, skipping whole cycles once the grid repeats.
+    pub fn run_for(&mut self, rule: impl Fn(Cell<'_, T>) -> T, generations: usize) {
+        let target = self.generation + generations;
+
+        if let Some(cycle) = self.find_cycle(&rule, generations) {
+            // the current grid is the one of `cycle.start + cycle.length`, the first repetition.
+            let remaining = (target - self.generation) % cycle.length;
+            for _ in 0..remaining {
+                self.step(&rule);
+            }
+        }
+
+        self.generation = target;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Automaton, Cell, Cycle, Neighbourhood};
+    use grid::{Grid, grid};
+
+    fn life(cell: Cell<'_, bool>) -> bool {
+        matches!(
+            (cell.value(), cell.count(|x| *x)),
+            (true, 2 | 3) | (false, 3)
+        )
+    }
+
+    /// Remove `true` cells with fewer than two `true` neighbours.
+    fn erode(cell: Cell<'_, bool>) -> bool {
+        *cell.value() && cell.count(|x| *x) >= 2
+    }
+
+    fn parse(rows: &[&str]) -> Grid<bool> {
+        let cells = rows
+            .iter()
+            .flat_map(|row| row.chars().map(|c| c == '#'))
+            .collect();
+        Grid::from_vec(cells, rows[0].len())
+    }
+
+    #[test]
+    fn steps_synchronously_and_in_place() {
+        let grid = parse(&["###.#"]);
+        let rule = |cell: Cell<'_, bool>| *cell.value() && cell.count(|x| *x) == 2;
+
+        let mut synchronous = Automaton::new(grid.clone(), Neighbourhood::VonNeumann);
+        assert_eq!(synchronous.step(rule), 3);
+        assert_eq!(synchronous.grid(), &parse(&[".#..."]));
+
+        // the middle cell sees that its left neighbour was already removed.
+        let mut in_place = Automaton::new(grid, Neighbourhood::VonNeumann);
+        assert_eq!(in_place.step_in_place(rule), 4);
+        assert_eq!(in_place.grid(), &parse(&["....."]));
+        assert_eq!(in_place.generation(), 1);
+    }
+
+    #[test]
+    fn runs_until_stable() {
+        let grid = parse(&["#....", "##...", ".###.", "..#.#", "....#"]);
+
+        let mut stepped = Automaton::new(grid.clone(), Neighbourhood::Moore);
+        let mut generations = 0;
+        while stepped.step(erode) > 0 {
+            generations += 1;
+        }
+
+        let mut automaton = Automaton::new(grid, Neighbourhood::Moore);
+        assert_eq!(automaton.run_until_stable(erode), generations);
+        assert_eq!(automaton.generation(), generations);
+        assert_eq!(automaton.grid(), stepped.grid());
+    }
+
+    #[test]
+    fn propagates_like_repeated_sweeps() {
+        let grid = parse(&["#.##.", "##.##", ".###.", "#.#.#", "##..#"]);
+
+        let mut swept = Automaton::new(grid.clone(), Neighbourhood::Moore);
+        let mut removed = 0;
+        loop {
+            let changed = swept.step_in_place(erode);
+            if changed == 0 {
+                break;
+            }
+            removed += changed;
+        }
+
+        let mut automaton = Automaton::new(grid, Neighbourhood::Moore);
+        assert_eq!(automaton.propagate(erode), removed);
+        assert_eq!(automaton.grid(), swept.grid());
+        assert_eq!(automaton.generation(), 0);
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let blinker = parse(&[".....", "..#..", "..#..", "..#..", "....."]);
+
+        let mut automaton = Automaton::new(blinker.clone(), Neighbourhood::Moore);
+        assert_eq!(
+            automaton.find_cycle(life, 10),
+            Some(Cycle {
+                start: 0,
+                length: 2
+            })
+        );
+
+        let mut automaton =
+            Automaton::new(grid![[true, false][false, false]], Neighbourhood::Moore);
+        let cycle = automaton.find_cycle(life, 10).unwrap();
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 1,
+                length: 1
+            }
+        );
+        assert_eq!(cycle.equivalent(1_000), 1);
+
+        let mut automaton = Automaton::new(blinker.clone(), Neighbourhood::Moore);
+        automaton.run_for(life, 1_000_001);
+        assert_eq!(automaton.generation(), 1_000_001);
+        let mut stepped = Automaton::new(blinker, Neighbourhood::Moore);
+        stepped.step(life);
+        assert_eq!(automaton.grid(), stepped.grid());
+    }
+}
@@ -1,110 +1,48 @@
-advent_of_code::solution!(1);
-
-#[derive(Debug)]
-enum Instruction {
-    Left(isize),
-    Right(isize),
-}
-
-struct Dial {
-    current_number: isize,
-}
-
-impl Dial {
-    pub fn new() -> Self {
-        Self { current_number: 50 }
-    }
-
-    pub fn spin(&mut self, instruction: &Instruction) -> isize {
-        let start = self.current_number;
-        // Returns times visited 0, not including a potential landing
-        let turn_count;
-        let turn_direction;
-        let mut times_visited_0;
-
-        match instruction {
-            Instruction::Left(steps) => {
-                times_visited_0 = steps.abs() / 100;
-                turn_count = steps % 100;
-                turn_direction = -1;
-            }
-            Instruction::Right(steps) => {
-                times_visited_0 = steps.abs() / 100;
-                turn_count = steps % 100;
-                turn_direction = 1;
-            }
-        }
-
-        self.current_number += turn_count * turn_direction;
-        if self.current_number == 0 {
-            times_visited_0 += 1;
-        }
-        if self.current_number < 0 {
-            self.current_number += 100;
-            if start != 0 {
-                times_visited_0 += 1;
-            }
-            if self.current_number == 0 {
-                times_visited_0 += 1;
-            }
-        }
-        if self.current_number >= 100 {
-            self.current_number -= 100;
-            times_visited_0 += 1;
-        }
+use advent_of_code::modular::Cyclic;
+use advent_of_code::parse::pattern;
 
-        return times_visited_0;
-    }
+advent_of_code::solution!(1);
 
-    fn is_0(&self) -> bool {
-        self.current_number == 0
-    }
-}
+type Dial = Cyclic<100>;
 
-fn parse_input(input: &str) -> Vec<Instruction> {
-    let mut steps = Vec::new();
-    for line in input.lines() {
-        let mut chars = line.chars();
-        let direction = chars.next().unwrap();
-        let count = chars.collect::<String>().parse::<isize>().unwrap();
-
-        steps.push(match direction {
-            'L' => Instruction::Left(count),
-            'R' => Instruction::Right(count),
-            _ => panic!(),
-        });
-    }
+/// Rotations of the dial, negative for turns to the left.
+fn parse_input(input: &str) -> Vec<i64> {
+    let steps: Vec<(char, i64)> = pattern::parse_lines("{dir:char}{count:i64}", input).unwrap();
 
     steps
+        .into_iter()
+        .map(|(direction, count)| match direction {
+            'L' => -count,
+            'R' => count,
+            _ => panic!(),
+        })
+        .collect()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let steps = parse_input(input);
-
-    let mut dial = Dial::new();
+    let mut dial = Dial::new(50);
     let mut landed_0_count = 0;
 
-    for step in steps {
-        dial.spin(&step);
-        if dial.is_0() {
+    for rotation in parse_input(input) {
+        dial.rotate(rotation);
+        if dial.value() == 0 {
             landed_0_count += 1;
         }
     }
 
-    Some(landed_0_count as u64)
+    Some(landed_0_count)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let steps = parse_input(input);
-    let mut dial = Dial::new();
-
+    let mut dial = Dial::new(50);
     let mut visited_0_count = 0;
 
-    for step in steps {
-        visited_0_count += dial.spin(&step);
+    for rotation in parse_input(input) {
+        visited_0_count += dial.passes(rotation, 0);
+        dial.rotate(rotation);
     }
 
-    Some(visited_0_count as u64)
+    Some(visited_0_count)
 }
 
 #[cfg(test)]
@@ -125,16 +63,13 @@ mod tests {
 
     #[test]
     fn test_part_two_example() {
-        let mut d = Dial::new();
-        assert_eq!(d.spin(&Instruction::Left(68)), 1);
-        assert_eq!(d.spin(&Instruction::Left(30)), 0);
-        assert_eq!(d.spin(&Instruction::Right(48)), 1);
-        assert_eq!(d.spin(&Instruction::Left(5)), 0);
-        assert_eq!(d.spin(&Instruction::Right(60)), 1);
-        assert_eq!(d.spin(&Instruction::Left(55)), 1);
-        assert_eq!(d.spin(&Instruction::Left(1)), 0);
-        assert_eq!(d.spin(&Instruction::Left(99)), 1);
-        assert_eq!(d.spin(&Instruction::Right(14)), 0);
-        assert_eq!(d.spin(&Instruction::Left(82)), 1);
+        let mut dial = Dial::new(50);
+        let rotations = [-68, -30, 48, -5, 60, -55, -1, -99, 14, -82];
+        let passes = [1, 0, 1, 0, 1, 1, 0, 1, 0, 1];
+
+        for (rotation, expected) in rotations.into_iter().zip(passes) {
+            assert_eq!(dial.passes(rotation, 0), expected);
+            dial.rotate(rotation);
+        }
     }
 }
@@ -1,72 +1,43 @@
-use std::str::FromStr;
+use std::ops::RangeInclusive;
 
-use fancy_regex::Regex;
+use advent_of_code::digits;
+use advent_of_code::parse::{self, pattern::Pattern};
 
 advent_of_code::solution!(2);
 
-fn parse_range(s: &str) -> (u64, u64) {
-    let mut nums = s.trim().split("-");
-    let num1 = nums.next().unwrap().parse::<u64>();
-    let num2 = nums.next().unwrap().parse::<u64>();
-    (num1.unwrap(), num2.unwrap())
+fn parse_ranges(input: &str) -> Vec<RangeInclusive<u64>> {
+    let pattern = Pattern::new("{}-{}").unwrap();
+    let mut parser = pattern.parser();
+
+    let line = parse::lines(input.trim()).next().unwrap();
+    line.split(",")
+        .map(|range| {
+            let (start, end) = parser.parse(range.trim()).unwrap();
+            start..=end
+        })
+        .collect()
 }
 
-fn is_valid_id(id: u64, reg: &Regex) -> bool {
-    let id_as_str = id.to_string();
-
-    return !reg.is_match(&id_as_str).unwrap();
-    if id_as_str.len() % 2 == 1 {
-        return true;
-    }
-
-    let middle = id_as_str.len() / 2;
-
-    for i in 0..middle {
-        if id_as_str.chars().nth(i).unwrap() != id_as_str.chars().nth(i + middle).unwrap() {
-            return true;
-        }
-    }
-    return false;
+fn sum(total: digits::Total) -> u64 {
+    u64::try_from(total.sum).unwrap()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut ranges = Vec::new();
-
-    for range_str in input.split(",") {
-        ranges.push(parse_range(range_str));
-    }
-    let r = Regex::from_str("^(.+)\\1$").unwrap();
-
-    let mut sum = 0;
-    for range in ranges {
-        for i in range.0..=range.1 {
-            if !is_valid_id(i, &r) {
-                sum += i
-            }
-        }
-    }
-    //println!("{sum}");
-    Some(sum)
+    Some(
+        parse_ranges(input)
+            .into_iter()
+            .map(|range| sum(digits::repeated_total(range, 2)))
+            .sum(),
+    )
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut ranges = Vec::new();
-
-    for range_str in input.split(",") {
-        ranges.push(parse_range(range_str));
-    }
-    let r = Regex::from_str("^(.+)\\1+$").unwrap();
-
-    let mut sum = 0;
-    for range in ranges {
-        for i in range.0..=range.1 {
-            if !is_valid_id(i, &r) {
-                sum += i
-            }
-        }
-    }
-    //println!("{sum}");
-    Some(sum)
+    Some(
+        parse_ranges(input)
+            .into_iter()
+            .map(|range| sum(digits::periodic_total(range)))
+            .sum(),
+    )
 }
 
 #[cfg(test)]
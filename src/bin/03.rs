@@ -1,3 +1,5 @@
+use advent_of_code::{digits, subsequence};
+
 advent_of_code::solution!(3);
 
 struct Bank {
@@ -6,41 +8,14 @@ struct Bank {
 
 impl Bank {
     pub fn from_str(s: &str) -> Self {
-        let mut batts = Vec::new();
-        let chars = s.chars();
-        for c in chars {
-            batts.push(c.to_digit(10).unwrap() as u8);
-        }
+        let batteries = s.chars().map(|c| c.to_digit(10).unwrap() as u8).collect();
 
-        Self { batteries: batts }
+        Self { batteries }
     }
 
     pub fn get_max_charge(&self, quantity: usize) -> usize {
-        let mut current_index = quantity;
-        let mut start = 0;
-
-        let mut charge_digits = Vec::new();
-        while current_index > 0 {
-            let mut largest_found = self.batteries[start];
-            let mut index = start;
-            for i in start..(self.batteries.len() - current_index + 1) {
-                // From last found largest digit, to last digit available whilst still having enough
-                if self.batteries[i] > largest_found {
-                    largest_found = self.batteries[i];
-                    index = i;
-                }
-            }
-
-            charge_digits.push(self.batteries[index]);
-            start = index + 1;
-            current_index -= 1;
-        }
-        let mut sum: u64 = 0;
-        for digit in charge_digits {
-            sum += digit as u64;
-            sum *= 10;
-        }
-        (sum / 10) as usize
+        let charge_digits = subsequence::largest(&self.batteries, quantity).unwrap();
+        digits::from_digits(&charge_digits).unwrap() as usize
     }
 }
 
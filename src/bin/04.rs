@@ -1,5 +1,10 @@
 use std::str::FromStr;
 
+use advent_of_code::automaton::{Automaton, Cell};
+use advent_of_code::bit_grid::BitGrid;
+use advent_of_code::geometry::Neighbourhood;
+use grid::Grid;
+
 advent_of_code::solution!(4);
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -29,186 +34,44 @@ impl FromStr for CellType {
     }
 }
 
-struct DynGrid<T> {
-    arena: Vec<T>,
-    width: usize,
-}
-
-impl<T: FromStr + PartialEq> FromStr for DynGrid<T> {
-    type Err = T::Err;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines().peekable();
-        let width = lines.peek().unwrap().trim().len();
-
-        let mut s = Self::new();
-        s.width = width;
-
-        for line in lines {
-            for c in line.trim().chars() {
-                s.arena.push(T::from_str(&c.to_string())?)
-            }
-        }
+fn parse_input(input: &str) -> Grid<CellType> {
+    let width = input.lines().next().unwrap().trim().len();
+    let cells = input
+        .lines()
+        .flat_map(|line| line.trim().chars())
+        .map(|c| CellType::from_str(&c.to_string()).unwrap())
+        .collect();
 
-        Ok(s)
-    }
+    Grid::from_vec(cells, width)
 }
 
-impl<T: PartialEq> DynGrid<T> {
-    pub fn new() -> Self {
-        Self {
-            arena: Vec::new(),
-            width: 0,
-        }
-    }
-
-    pub fn set_width(&mut self, width: usize) {
-        self.width = width;
-    }
-
-    pub fn width(&self) -> usize {
-        self.width
-    }
-
-    pub fn height(&self) -> usize {
-        self.arena.len() / self.width
-    }
-
-    pub fn get_cell_signed(&self, x: isize, y: isize) -> Option<&T> {
-        if x < 0 || x >= self.width().try_into().unwrap() {
-            return None;
-        }
-        if y < 0 || y >= self.height().try_into().unwrap() {
-            return None;
-        }
-
-        return Some(&self.arena[x as usize + y as usize * self.width()]);
-    }
-
-    pub fn get_cell(&self, x: usize, y: usize) -> Option<&T> {
-        if x >= self.width() {
-            return None;
-        }
-        if y >= self.height() {
-            return None;
-        }
-
-        return Some(&self.arena[x + y * self.width()]);
-    }
-
-    pub fn get_cell_signed_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
-        if x < 0 || x >= self.width().try_into().unwrap() {
-            return None;
-        }
-        if y < 0 || y >= self.height().try_into().unwrap() {
-            return None;
-        }
-
-        let w = self.width();
-        return Some(&mut self.arena[x as usize + y as usize * w]);
-    }
-
-    pub fn get_cell_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
-        if x >= self.width() {
-            return None;
-        }
-        if y >= self.height() {
-            return None;
-        }
-        let w = self.width();
-        return Some(&mut self.arena[x + y * w]);
-    }
-
-    pub fn get_neighbours(&self, x: usize, y: usize) -> Vec<Option<&T>> {
-        let mut neighbours = Vec::new();
-        for iy in -1..=1 {
-            for ix in -1..=1 {
-                if ix == 0 && iy == 0 {
-                    continue;
-                }
-                neighbours.push(self.get_cell_signed(x as isize + ix, y as isize + iy));
-            }
-        }
-
-        neighbours
-    }
-
-    pub fn set_cell(&mut self, x: usize, y: usize, t: T) -> Result<(), ()> {
-        let c = self.get_cell_mut(x, y).ok_or(())?;
-
-        *c = t;
-
-        Ok(())
-    }
-
-    pub fn count_matching_neighbours(&self, x: usize, y: usize, to_match: T) -> usize {
-        let mut count = 0;
-
-        for n in self.get_neighbours(x, y) {
-            if let Some(t) = n {
-                if *t == to_match {
-                    count += 1;
-                }
-            }
-        }
-
-        count
-    }
+/// Paper with fewer than 4 paper neighbours can be taken away.
+fn accessible(paper: &BitGrid) -> BitGrid {
+    paper & &paper.neighbours_matching(Neighbourhood::Moore, |n| n < 4)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let map = DynGrid::<CellType>::from_str(input).unwrap();
+    let paper = BitGrid::from_grid(&parse_input(input), |c| *c == CellType::Paper);
+    let available_count = accessible(&paper).count_ones();
 
-    let mut available_count = 0;
-
-    for y in 0..map.height() {
-        for x in 0..map.width() {
-            if let Some(c) = map.get_cell(x, y) {
-                if *c == CellType::Floor {
-                    continue;
-                }
-            }
-
-            let paper_count = map.count_matching_neighbours(x, y, CellType::Paper);
+    Some(available_count as u64)
+}
 
-            if paper_count < 4 {
-                available_count += 1;
-            }
-        }
+/// Cell-wise version of [`accessible`], turning accessible paper into floor.
+fn remove_accessible(cell: Cell<'_, CellType>) -> CellType {
+    if *cell.value() == CellType::Paper && cell.count(|c| *c == CellType::Paper) < 4 {
+        CellType::Floor
+    } else {
+        *cell.value()
     }
-
-    Some(available_count)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut map = DynGrid::<CellType>::from_str(input).unwrap();
-
-    let mut available_count = 0;
-
-    let mut total_changed = 1;
-
-    while total_changed > 0 {
-        total_changed = 0;
-        for y in 0..map.height() {
-            for x in 0..map.width() {
-                let paper_count = map.count_matching_neighbours(x, y, CellType::Paper);
-
-                if paper_count < 4 {
-                    let c = map.get_cell_mut(x, y).unwrap();
-                    match c {
-                        CellType::Floor => {}
-                        CellType::Paper => {
-                            available_count += 1;
-                            total_changed += 1;
-                            *c = CellType::Floor;
-                        }
-                    }
-                }
-            }
-        }
-    }
+    // removing paper only frees up its neighbours, so only those are checked again.
+    let mut map = Automaton::new(parse_input(input), Neighbourhood::Moore);
+    let available_count = map.propagate(remove_accessible);
 
-    Some(available_count)
+    Some(available_count as u64)
 }
 
 #[cfg(test)]
@@ -1,21 +1,17 @@
+use advent_of_code::parse::{self, pattern::Pattern};
 use advent_of_code::{Endpoint, Range};
 
 advent_of_code::solution!(5);
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut lines = input.lines();
+    let mut sections = parse::sections(input);
+    let pattern = Pattern::new("{start}-{end}").unwrap();
+    let mut parser = pattern.parser::<(u64, u64)>();
 
     let mut ranges = vec![];
     let mut items: Vec<i64> = vec![];
-    while let Some(cur_line) = lines.next() {
-        if cur_line == "" {
-            break;
-        }
-
-        let (start, stop) = cur_line.split_once("-").unwrap();
-        let start_num: u64 = start.parse().unwrap();
-
-        let end_num: u64 = stop.parse().unwrap();
+    for cur_line in sections.next().unwrap().lines() {
+        let (start_num, end_num) = parser.parse(cur_line).unwrap();
 
         ranges.push(Range::new(
             Endpoint::Inclusive(start_num),
@@ -23,8 +19,8 @@ pub fn part_one(input: &str) -> Option<u64> {
         ));
     }
 
-    while let Some(cur_line) = lines.next() {
-        items.push(cur_line.parse().unwrap());
+    for cur_line in sections.next().unwrap().lines() {
+        items.push(cur_line.as_str().parse().unwrap());
     }
     let mut valid_items = 0;
     for item in items {
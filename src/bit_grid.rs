@@ -0,0 +1,472 @@
+//! A grid of booleans packed into bits, for fast operations on whole maps.
+//!
+//! Every row of a [`BitGrid`] is stored in 64-bit words. Shifts, boolean operations and neighbour counts
+//! work on whole words, so they process 64 cells at a time and vectorize well. Neighbours are counted with
+//! bit-sliced adders: the counts of 64 cells are kept as four words holding one bit of each count.
+//!
+//! ```
+//! use advent_of_code::geometry::Neighbourhood;
+//! use advent_of_code::bit_grid::BitGrid;
+//!
+//! let grid: BitGrid = ".#.\n.#.\n.#.".parse().unwrap();
+//! // one generation of the game of life turns the blinker.
+//! let next = grid.step(Neighbourhood::Moore, |alive, n| n == 3 || (alive && n == 2));
+//! assert_eq!(next.to_string(), "...\n###\n...");
+//! ```
+use std::fmt::Display;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use std::str::FromStr;
+
+use grid::Grid;
+
+use crate::geometry::{Direction8, Neighbourhood, Position};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A fixed-size grid of booleans. Bits beyond the last column of a row are always zero.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    rows: usize,
+    cols: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// A grid of `rows` by `cols` cells that are all unset.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let words_per_row = cols.div_ceil(WORD_BITS);
+        BitGrid {
+            rows,
+            cols,
+            words_per_row,
+            words: vec![0; rows * words_per_row],
+        }
+    }
+
+    /// The cells of `grid` for which `predicate` holds.
+    pub fn from_grid<T>(grid: &Grid<T>, predicate: impl Fn(&T) -> bool) -> Self {
+        let mut bits = BitGrid::new(grid.rows(), grid.cols());
+        for (position, value) in grid.indexed_iter() {
+            if predicate(value) {
+                bits.set(position, true);
+            }
+        }
+        bits
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        let cells = (0..self.rows)
+            .flat_map(|row| (0..self.cols).map(move |col| (row, col)))
+            .map(|position| self.get(position))
+            .collect();
+        Grid::from_vec(cells, self.cols)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn word_index(&self, (row, col): Position) -> (usize, u64) {
+        assert!(
+            row < self.rows && col < self.cols,
+            "position ({row}, {col}) is outside of a {}x{} grid",
+            self.rows,
+            self.cols
+        );
+        (
+            row * self.words_per_row + col / WORD_BITS,
+            1 << (col % WORD_BITS),
+        )
+    }
+
+    /// # Panics
+    /// Panics if `position` is outside of the grid.
+    pub fn get(&self, position: Position) -> bool {
+        let (index, bit) = self.word_index(position);
+        self.words[index] & bit != 0
+    }
+
+    /// # Panics
+    /// Panics if `position` is outside of the grid.
+    pub fn set(&mut self, position: Position, value: bool) {
+        let (index, bit) = self.word_index(position);
+        if value {
+            self.words[index] |= bit;
+        } else {
+            self.words[index] &= !bit;
+        }
+    }
+
+    /// Number of set cells.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|w| *w != 0)
+    }
+
+    /// Positions of the set cells, row by row.
+    pub fn iter_ones(&self) -> impl Iterator<Item = Position> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, &word)| {
+                let row = index / self.words_per_row;
+                let first_col = index % self.words_per_row * WORD_BITS;
+                std::iter::successors((word != 0).then_some(word), |w| {
+                    Some(w & (w - 1)).filter(|w| *w != 0)
+                })
+                .map(move |w| (row, first_col + w.trailing_zeros() as usize))
+            })
+    }
+
+    /// The mask of the valid bits in the last word of a row.
+    fn tail_mask(&self) -> u64 {
+        match self.cols % WORD_BITS {
+            0 => u64::MAX,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    /// Clear the bits beyond the last column.
+    fn clear_padding(&mut self) {
+        let mask = self.tail_mask();
+        if self.words_per_row > 0 {
+            for row in self.words.chunks_exact_mut(self.words_per_row) {
+                *row.last_mut().unwrap() &= mask;
+            }
+        }
+    }
+
+    /// Move every cell one step in `direction`. Cells moved off the grid are lost and the cells moved in are
+    /// unset.
+    #[must_use]
+    pub fn shifted(&self, direction: Direction8) -> BitGrid {
+        let offset = direction.offset();
+        let mut shifted = BitGrid::new(self.rows, self.cols);
+        let n = self.words_per_row;
+
+        for row in 0..self.rows {
+            let Some(source) = row
+                .checked_add_signed(-offset.y as isize)
+                .filter(|r| *r < self.rows)
+            else {
+                continue;
+            };
+
+            let from = &self.words[source * n..(source + 1) * n];
+            let to = &mut shifted.words[row * n..(row + 1) * n];
+            for i in 0..n {
+                // bit `j` of a word is column `j`, so moving east shifts towards the high bits.
+                to[i] = match offset.x {
+                    1 => from[i] << 1 | if i > 0 { from[i - 1] >> 63 } else { 0 },
+                    -1 => from[i] >> 1 | from.get(i + 1).map_or(0, |w| w << 63),
+                    _ => from[i],
+                };
+            }
+        }
+
+        shifted.clear_padding();
+        shifted
+    }
+
+    /// The neighbour counts of all cells, as four bit planes per word: bit `j` of plane `b` is bit `b` of the
+    /// count of the cell at bit `j` of the word.
+    fn neighbour_planes(&self, neighbourhood: Neighbourhood) -> Vec<[u64; 4]> {
+        // the neighbours of a cell are the cells shifted onto it from the opposite direction.
+        let shifted: Vec<BitGrid> = neighbourhood
+            .directions()
+            .map(|d| self.shifted(d.opposite()))
+            .collect();
+
+        (0..self.words.len())
+            .map(|index| {
+                let mut planes = [0_u64; 4];
+                for grid in &shifted {
+                    let mut carry = grid.words[index];
+                    for plane in &mut planes {
+                        let next_carry = *plane & carry;
+                        *plane ^= carry;
+                        carry = next_carry;
+                    }
+                }
+                planes
+            })
+            .collect()
+    }
+
+    /// The bits of the cells whose count, as given by `planes`, is one of `counts`.
+    fn matching(planes: &[u64; 4], counts: &[u8]) -> u64 {
+        counts.iter().fold(0, |word, count| {
+            word | planes
+                .iter()
+                .enumerate()
+                .fold(u64::MAX, |matches, (bit, plane)| {
+                    matches
+                        & if count >> bit & 1 == 1 {
+                            *plane
+                        } else {
+                            !plane
+                        }
+                })
+        })
+    }
+
+    /// The cells whose number of set neighbours satisfies `predicate`, for counts from `0` to `8`.
+    #[must_use]
+    pub fn neighbours_matching(
+        &self,
+        neighbourhood: Neighbourhood,
+        predicate: impl Fn(u8) -> bool,
+    ) -> BitGrid {
+        let counts: Vec<u8> = (0..=8).filter(|c| predicate(*c)).collect();
+
+        let mut result = BitGrid::new(self.rows, self.cols);
+        for (word, planes) in result
+            .words
+            .iter_mut()
+            .zip(self.neighbour_planes(neighbourhood))
+        {
+            *word = Self::matching(&planes, &counts);
+        }
+
+        result.clear_padding();
+        result
+    }
+
+    /// Number of set neighbours of a single cell.
+    pub fn neighbour_count(&self, position: Position, neighbourhood: Neighbourhood) -> u8 {
+        let count = neighbourhood
+            .positions(position, self.rows, self.cols)
+            .filter(|p| self.get(*p))
+            .count();
+        u8::try_from(count).unwrap()
+    }
+
+    /// One synchronous generation of an automaton: `rule(alive, neighbours)` decides whether a cell is set in
+    /// the next generation, from its value and its number of set neighbours.
+    #[must_use]
+    pub fn step(&self, neighbourhood: Neighbourhood, rule: impl Fn(bool, u8) -> bool) -> BitGrid {
+        let alive: Vec<u8> = (0..=8).filter(|n| rule(true, *n)).collect();
+        let born: Vec<u8> = (0..=8).filter(|n| rule(false, *n)).collect();
+
+        let mut result = BitGrid::new(self.rows, self.cols);
+        for ((word, planes), current) in result
+            .words
+            .iter_mut()
+            .zip(self.neighbour_planes(neighbourhood))
+            .zip(&self.words)
+        {
+            *word = current & Self::matching(&planes, &alive)
+                | !current & Self::matching(&planes, &born);
+        }
+
+        result.clear_padding();
+        result
+    }
+}
+
+macro_rules! bit_operation {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt) => {
+        impl $assign_trait<&BitGrid> for BitGrid {
+            /// # Panics
+            /// Panics if the grids differ in size.
+            fn $assign_method(&mut self, other: &BitGrid) {
+                assert_eq!(
+                    (self.rows, self.cols),
+                    (other.rows, other.cols),
+                    "grids differ in size"
+                );
+                for (a, b) in self.words.iter_mut().zip(&other.words) {
+                    *a = *a $op *b;
+                }
+            }
+        }
+
+        impl $trait for &BitGrid {
+            type Output = BitGrid;
+
+            fn $method(self, other: &BitGrid) -> BitGrid {
+                let mut result = self.clone();
+                result.$assign_method(other);
+                result
+            }
+        }
+    };
+}
+
+bit_operation!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+bit_operation!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+bit_operation!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut result = self.clone();
+        for word in &mut result.words {
+            *word = !*word;
+        }
+        result.clear_padding();
+        result
+    }
+}
+
+impl FromStr for BitGrid {
+    type Err = String;
+
+    /// Parse lines of `#` for set and `.` for unset cells.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.lines().map(str::trim).collect();
+        let cols = lines.first().map_or(0, |line| line.len());
+        let mut grid = BitGrid::new(lines.len(), cols);
+
+        for (row, line) in lines.iter().enumerate() {
+            if line.len() != cols {
+                return Err(format!(
+                    "line {} has {} columns, expected {cols}",
+                    row + 1,
+                    line.len()
+                ));
+            }
+            for (col, c) in line.chars().enumerate() {
+                match c {
+                    '#' => grid.set((row, col), true),
+                    '.' => {}
+                    _ => return Err(format!("unexpected `{c}` in line {}", row + 1)),
+                }
+            }
+        }
+
+        Ok(grid)
+    }
+}
+
+impl Display for BitGrid {
+    /// Render rows of `#` for set and `.` for unset cells.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.rows {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            for col in 0..self.cols {
+                write!(f, "{}", if self.get((row, col)) { '#' } else { '.' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitGrid;
+    use crate::automaton::{Automaton, Cell};
+    use crate::geometry::{Direction8, Neighbourhood, Point2};
+
+    /// A pseudo-random grid that spans several words per row.
+    fn noise(rows: usize, cols: usize) -> BitGrid {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut grid = BitGrid::new(rows, cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                grid.set((row, col), state.is_multiple_of(3));
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn sets_and_counts_bits() {
+        let mut grid = BitGrid::new(3, 130);
+        grid.set((0, 0), true);
+        grid.set((1, 64), true);
+        grid.set((2, 129), true);
+        grid.set((2, 129), true);
+        assert!(grid.get((1, 64)));
+        assert!(!grid.get((1, 63)));
+        assert_eq!(grid.count_ones(), 3);
+        assert_eq!(
+            grid.iter_ones().collect::<Vec<_>>(),
+            [(0, 0), (1, 64), (2, 129)]
+        );
+
+        grid.set((0, 0), false);
+        assert_eq!((!&grid).count_ones(), 3 * 130 - 2);
+        assert!(!(&grid ^ &grid).any());
+    }
+
+    #[test]
+    fn shifts_like_cells() {
+        let grid = noise(5, 130);
+
+        for direction in Direction8::ALL {
+            let shifted = grid.shifted(direction);
+            for row in 0..grid.rows() {
+                for col in 0..grid.cols() {
+                    let source = Point2::from_position((row, col)) - direction.offset();
+                    let expected = source
+                        .position_within(grid.rows(), grid.cols())
+                        .is_some_and(|p| grid.get(p));
+                    assert_eq!(
+                        shifted.get((row, col)),
+                        expected,
+                        "{direction:?} ({row}, {col})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn counts_neighbours() {
+        let grid = noise(6, 70);
+
+        for neighbourhood in [Neighbourhood::Moore, Neighbourhood::VonNeumann] {
+            for count in 0..=8 {
+                let matching = grid.neighbours_matching(neighbourhood, |n| n == count);
+                for row in 0..grid.rows() {
+                    for col in 0..grid.cols() {
+                        assert_eq!(
+                            matching.get((row, col)),
+                            grid.neighbour_count((row, col), neighbourhood) == count
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn steps_like_the_automaton() {
+        let life = |alive: bool, n: u8| n == 3 || (alive && n == 2);
+        let mut grid = noise(20, 100);
+        let mut automaton = Automaton::new(grid.to_grid(), Neighbourhood::Moore);
+
+        for _ in 0..10 {
+            grid = grid.step(Neighbourhood::Moore, life);
+            automaton.step(|cell: Cell<'_, bool>| life(*cell.value(), cell.count(|x| *x) as u8));
+            assert_eq!(&grid.to_grid(), automaton.grid());
+        }
+    }
+
+    #[test]
+    fn parses_and_renders() {
+        let grid: BitGrid = "#..\n.##".parse().unwrap();
+        assert_eq!(grid.to_string(), "#..\n.##");
+        assert_eq!(
+            BitGrid::from_grid(&grid.to_grid(), |x| !x).to_string(),
+            ".##\n#.."
+        );
+        assert!("#.\n#".parse::<BitGrid>().is_err());
+        assert!("#x".parse::<BitGrid>().is_err());
+    }
+}
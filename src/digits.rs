@@ -0,0 +1,299 @@
+//! Decimal digits of numbers, and arithmetic on numbers formed by repeating a block of digits, like `1212`
+//! or `777`.
+//!
+//! A number that repeats the `k`-digit block `b` for `m` times equals `b * (10^(k*(m-1)) + ... + 10^k + 1)`.
+//! The numbers of such a shape within a range therefore form an arithmetic progression and can be counted
+//! and summed without enumerating them.
+//!
+//! ```
+//! use advent_of_code::digits;
+//!
+//! // 11, 22, ..., 99 and 1010, 1111, ..., 9999.
+//! assert_eq!(digits::repeated_total(10..=9999, 2).count, 9 + 90);
+//! // 111 and 222 repeat a block three times, 1010 twice.
+//! assert_eq!(digits::periodic_total(100..=1010).sum, 111 + 222 + 333 + 444 + 555 + 666 + 777 + 888 + 999 + 1010);
+//! ```
+use std::ops::RangeInclusive;
+
+/// Number of decimal digits of `n`, `1` for zero.
+pub fn digit_count(n: u64) -> u32 {
+    n.checked_ilog10().map_or(1, |x| x + 1)
+}
+
+/// Returns whether the digits of `n` are a block repeated exactly `repeats` times, e.g. `123123` for `2`.
+pub fn is_repetition(n: u64, repeats: u32) -> bool {
+    let len = digit_count(n);
+    if repeats == 0 || !len.is_multiple_of(repeats) {
+        return false;
+    }
+
+    let factor = repeat_factor(len / repeats, repeats);
+    u128::from(n).is_multiple_of(factor)
+}
+
+/// Returns whether the digits of `n` are a block repeated at least twice.
+pub fn is_periodic(n: u64) -> bool {
+    (2..=digit_count(n)).any(|repeats| is_repetition(n, repeats))
+}
+
+/// The decimal digits of `n`, most significant first.
+pub fn to_digits(n: u64) -> Vec<u8> {
+    #[allow(clippy::cast_possible_truncation)]
+    let mut digits: Vec<u8> = std::iter::successors(Some(n), |n| (*n >= 10).then_some(n / 10))
+        .map(|n| (n % 10) as u8)
+        .collect();
+    digits.reverse();
+    digits
+}
+
+/// The number with the decimal `digits`, most significant first. Returns `None` if a digit is not below
+/// `10` or the number does not fit into `u64`. No digits make `0`.
+pub fn from_digits(digits: &[u8]) -> Option<u64> {
+    digits.iter().try_fold(0_u64, |n, &digit| {
+        if digit >= 10 {
+            return None;
+        }
+        n.checked_mul(10)?.checked_add(u64::from(digit))
+    })
+}
+
+/// The factor that repeats a `block_len`-digit block `repeats` times, i.e. `1001` for `(3, 2)`.
+fn repeat_factor(block_len: u32, repeats: u32) -> u128 {
+    (0..repeats).fold(0, |factor, _| factor * 10_u128.pow(block_len) + 1)
+}
+
+/// Count and sum of a set of numbers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Total {
+    pub count: u64,
+    pub sum: u128,
+}
+
+impl std::ops::Add for Total {
+    type Output = Total;
+
+    fn add(self, other: Total) -> Total {
+        Total {
+            count: self.count + other.count,
+            sum: self.sum + other.sum,
+        }
+    }
+}
+
+impl std::ops::Sub for Total {
+    type Output = Total;
+
+    fn sub(self, other: Total) -> Total {
+        Total {
+            count: self.count - other.count,
+            sum: self.sum - other.sum,
+        }
+    }
+}
+
+impl std::iter::Sum for Total {
+    fn sum<I: Iterator<Item = Total>>(iter: I) -> Total {
+        iter.fold(Total::default(), |a, b| a + b)
+    }
+}
+
+/// The numbers in a range that repeat a block of a fixed length a fixed number of times.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Repetitions {
+    factor: u128,
+    blocks: RangeInclusive<u128>,
+}
+
+impl Repetitions {
+    /// The numbers in `range` that repeat a `block_len`-digit block `repeats` times.
+    /// Blocks have no leading zeros, so `0101` is not a repetition of `01`.
+    ///
+    /// # Panics
+    /// Panics if `block_len` or `repeats` is zero.
+    pub fn new(range: RangeInclusive<u64>, block_len: u32, repeats: u32) -> Self {
+        assert!(
+            block_len > 0 && repeats > 0,
+            "block length and repeats must be positive"
+        );
+
+        let factor = repeat_factor(block_len, repeats);
+        let (start, end) = (u128::from(*range.start()), u128::from(*range.end()));
+
+        let first = 10_u128.pow(block_len - 1).max(start.div_ceil(factor));
+        let last = (10_u128.pow(block_len) - 1).min(end / factor);
+
+        Repetitions {
+            factor,
+            blocks: first..=last,
+        }
+    }
+
+    /// Count and sum of the numbers, without enumerating them.
+    pub fn total(&self) -> Total {
+        let (first, last) = (*self.blocks.start(), *self.blocks.end());
+        if first > last {
+            return Total::default();
+        }
+
+        let count = last - first + 1;
+        #[allow(clippy::cast_possible_truncation)]
+        Total {
+            count: count as u64,
+            sum: self.factor * ((first + last) * count / 2),
+        }
+    }
+
+    /// Iterate over the numbers in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        #[allow(clippy::cast_possible_truncation)]
+        self.blocks
+            .clone()
+            .map(|block| (block * self.factor) as u64)
+    }
+}
+
+/// The lengths of the numbers in `range`.
+fn lengths(range: &RangeInclusive<u64>) -> RangeInclusive<u32> {
+    digit_count(*range.start())..=digit_count(*range.end())
+}
+
+/// Count and sum of the numbers in `range` that are a block of any length repeated exactly `repeats` times.
+pub fn repeated_total(range: RangeInclusive<u64>, repeats: u32) -> Total {
+    lengths(&range)
+        .filter(|len| len.is_multiple_of(repeats))
+        .map(|len| Repetitions::new(range.clone(), len / repeats, repeats).total())
+        .sum()
+}
+
+/// Möbius function of small `n`.
+fn mobius(mut n: u32) -> i32 {
+    let mut result = 1;
+    let mut p = 2;
+
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            result = -result;
+        }
+        p += 1;
+    }
+
+    if n > 1 { -result } else { result }
+}
+
+/// Count and sum of the numbers in `range` that are a block repeated at least twice. Numbers with several
+/// such shapes, like `222222`, are counted once.
+pub fn periodic_total(range: RangeInclusive<u64>) -> Total {
+    let mut added = Total::default();
+    let mut removed = Total::default();
+
+    // a number has period `d` for a divisor `d` of its length if it repeats a `d`-digit block.
+    // by inclusion-exclusion over the prime factors of the length, the numbers with any proper period are
+    // counted by the numbers with period `d`, weighted with `-μ(len / d)`.
+    for len in lengths(&range) {
+        for block_len in (1..len).filter(|d| len.is_multiple_of(*d)) {
+            let total = Repetitions::new(range.clone(), block_len, len / block_len).total();
+            match mobius(len / block_len) {
+                -1 => added = added + total,
+                1 => removed = removed + total,
+                _ => {}
+            }
+        }
+    }
+
+    added - removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Repetitions, Total, digit_count, from_digits, is_periodic, is_repetition, mobius,
+        periodic_total, repeated_total, to_digits,
+    };
+
+    fn brute_force(range: std::ops::RangeInclusive<u64>, filter: impl Fn(u64) -> bool) -> Total {
+        range
+            .filter(|n| filter(*n))
+            .map(|n| Total {
+                count: 1,
+                sum: u128::from(n),
+            })
+            .sum()
+    }
+
+    #[test]
+    fn checks_repetitions() {
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(1000), 4);
+        assert!(is_repetition(123_123, 2));
+        assert!(is_repetition(777, 3));
+        assert!(!is_repetition(777, 2));
+        assert!(!is_repetition(1231, 2));
+        assert!(is_periodic(222_222));
+        assert!(!is_periodic(7));
+        assert_eq!([1, 2, 3, 4, 5, 6, 12].map(mobius), [1, -1, -1, 0, -1, 1, 0]);
+    }
+
+    #[test]
+    fn converts_digits() {
+        assert_eq!(to_digits(0), [0]);
+        assert_eq!(to_digits(9_087), [9, 0, 8, 7]);
+        assert_eq!(from_digits(&[9, 0, 8, 7]), Some(9_087));
+        assert_eq!(from_digits(&[]), Some(0));
+        assert_eq!(from_digits(&to_digits(u64::MAX)), Some(u64::MAX));
+        assert_eq!(
+            from_digits(&[1, 8, 4, 4, 6, 7, 4, 4, 0, 7, 3, 7, 0, 9, 5, 5, 1, 6, 1, 6]),
+            None
+        );
+        assert_eq!(from_digits(&[1, 10]), None);
+    }
+
+    #[test]
+    fn enumerates_repetitions() {
+        let repetitions = Repetitions::new(95..=2000, 2, 2);
+        assert_eq!(
+            repetitions.iter().collect::<Vec<_>>(),
+            [1010, 1111, 1212, 1313, 1414, 1515, 1616, 1717, 1818, 1919]
+        );
+        assert_eq!(repetitions.total().count, 10);
+        assert_eq!(repetitions.total().sum, 14_645);
+        assert_eq!(Repetitions::new(1..=9, 1, 2).total(), Total::default());
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for range in [
+            1..=100_000,
+            998..=1012,
+            222_220..=222_224,
+            95_000..=1_300_000,
+        ] {
+            assert_eq!(
+                repeated_total(range.clone(), 2),
+                brute_force(range.clone(), |n| is_repetition(n, 2)),
+                "{range:?}"
+            );
+            assert_eq!(
+                periodic_total(range.clone()),
+                brute_force(range.clone(), is_periodic),
+                "{range:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn handles_large_numbers() {
+        let total = periodic_total(u64::MAX - 1_000_000_000..=u64::MAX);
+        assert_eq!(total, Total::default());
+
+        let total = repeated_total(100_000_000_000_000_000..=999_999_999_999_999_999, 2);
+        assert_eq!(total.count, 900_000_000);
+
+        // 20-digit repetitions end at `18446744071844674407`, with blocks up to `1844674407`.
+        let total = repeated_total(1_000_000_000_000_000_000..=u64::MAX, 2);
+        assert_eq!(total.count, 844_674_408);
+    }
+}
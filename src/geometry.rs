@@ -0,0 +1,535 @@
+//! Points and directions for 2D and 3D puzzles.
+//!
+//! Coordinates are signed, so that points can leave a grid and be checked afterwards. `y` grows downwards,
+//! like the rows of a puzzle input, so [`Direction4::North`] is `(0, -1)` and turning right goes clockwise
+//! on screen. Grid positions are `(row, column)` pairs, which convert to points with `x = column` and
+//! `y = row`.
+//!
+//! ```
+//! use advent_of_code::geometry::{Direction4, Point2};
+//!
+//! let mut position = Point2::new(0, 0);
+//! let mut facing = Direction4::North;
+//! for turn in ['R', 'R', 'L'] {
+//!     facing = if turn == 'R' { facing.turn_right() } else { facing.turn_left() };
+//!     position = position + facing * 2;
+//! }
+//! assert_eq!(position, Point2::new(4, 2));
+//! assert_eq!(position.manhattan(Point2::new(0, 0)), 6);
+//! assert_eq!(position.position_within(3, 5), Some((2, 4)));
+//! ```
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Position of a cell in a grid as `(row, column)`, like the indices of [`grid::Grid`].
+pub type Position = (usize, usize);
+
+/// A point, or an offset between two points, in 2D.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point2 { x, y }
+    }
+
+    /// The point of the grid position `(row, column)`.
+    ///
+    /// # Panics
+    /// Panics if a coordinate does not fit into `i64`.
+    pub fn from_position((row, col): Position) -> Self {
+        Point2 {
+            x: i64::try_from(col).expect("column fits into i64"),
+            y: i64::try_from(row).expect("row fits into i64"),
+        }
+    }
+
+    /// The grid position `(row, column)` of the point, if both coordinates are non-negative.
+    pub fn position(self) -> Option<Position> {
+        Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+    }
+
+    /// The grid position `(row, column)` of the point, if it lies within a grid of `rows` by `cols` cells.
+    pub fn position_within(self, rows: usize, cols: usize) -> Option<Position> {
+        self.position()
+            .filter(|&(row, col)| row < rows && col < cols)
+    }
+
+    pub fn manhattan(self, other: Point2) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Distance when diagonal steps are allowed, like a king on a chess board.
+    pub fn chebyshev(self, other: Point2) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Rotate by 90 degrees around the origin, clockwise on screen.
+    #[must_use]
+    pub fn rotate_right(self) -> Self {
+        Point2::new(-self.y, self.x)
+    }
+
+    /// Rotate by 90 degrees around the origin, counterclockwise on screen.
+    #[must_use]
+    pub fn rotate_left(self) -> Self {
+        Point2::new(self.y, -self.x)
+    }
+
+    /// The four orthogonally adjacent points, in the order of [`Direction4::ALL`].
+    pub fn neighbours4(self) -> impl Iterator<Item = Point2> {
+        Direction4::ALL.into_iter().map(move |d| self + d)
+    }
+
+    /// The eight orthogonally and diagonally adjacent points, in the order of [`Direction8::ALL`].
+    pub fn neighbours8(self) -> impl Iterator<Item = Point2> {
+        Direction8::ALL.into_iter().map(move |d| self + d)
+    }
+}
+
+impl Add for Point2 {
+    type Output = Point2;
+
+    fn add(self, other: Point2) -> Point2 {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Point2;
+
+    fn sub(self, other: Point2) -> Point2 {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Point2 {
+    type Output = Point2;
+
+    fn neg(self) -> Point2 {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point2 {
+    type Output = Point2;
+
+    fn mul(self, factor: i64) -> Point2 {
+        Point2::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl AddAssign for Point2 {
+    fn add_assign(&mut self, other: Point2) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point2 {
+    fn sub_assign(&mut self, other: Point2) {
+        *self = *self - other;
+    }
+}
+
+impl Add<Direction4> for Point2 {
+    type Output = Point2;
+
+    fn add(self, direction: Direction4) -> Point2 {
+        self + direction.offset()
+    }
+}
+
+impl Add<Direction8> for Point2 {
+    type Output = Point2;
+
+    fn add(self, direction: Direction8) -> Point2 {
+        self + direction.offset()
+    }
+}
+
+impl AddAssign<Direction4> for Point2 {
+    fn add_assign(&mut self, direction: Direction4) {
+        *self = *self + direction;
+    }
+}
+
+impl AddAssign<Direction8> for Point2 {
+    fn add_assign(&mut self, direction: Direction8) {
+        *self = *self + direction;
+    }
+}
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+/// A point, or an offset between two points, in 3D.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point3) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev(self, other: Point3) -> u64 {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    /// The six points sharing a face with this one.
+    pub fn neighbours6(self) -> impl Iterator<Item = Point3> {
+        const OFFSETS: [Point3; 6] = [
+            Point3::new(-1, 0, 0),
+            Point3::new(1, 0, 0),
+            Point3::new(0, -1, 0),
+            Point3::new(0, 1, 0),
+            Point3::new(0, 0, -1),
+            Point3::new(0, 0, 1),
+        ];
+        OFFSETS.into_iter().map(move |offset| self + offset)
+    }
+
+    /// The 26 points sharing a face, an edge or a corner with this one.
+    pub fn neighbours26(self) -> impl Iterator<Item = Point3> {
+        (-1..=1)
+            .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| Point3::new(x, y, z))))
+            .filter(|offset| *offset != Point3::ORIGIN)
+            .map(move |offset| self + offset)
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Point3 {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, factor: i64) -> Point3 {
+        Point3::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, other: Point3) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, other: Point3) {
+        *self = *self - other;
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+/// One of the four orthogonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction4 {
+    /// All directions, clockwise from north.
+    pub const ALL: [Direction4; 4] = [
+        Direction4::North,
+        Direction4::East,
+        Direction4::South,
+        Direction4::West,
+    ];
+
+    pub fn offset(self) -> Point2 {
+        match self {
+            Direction4::North => Point2::new(0, -1),
+            Direction4::East => Point2::new(1, 0),
+            Direction4::South => Point2::new(0, 1),
+            Direction4::West => Point2::new(-1, 0),
+        }
+    }
+
+    fn from_index(index: usize) -> Self {
+        Direction4::ALL[index % 4]
+    }
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Direction4::from_index(self as usize + 1)
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Direction4::from_index(self as usize + 3)
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        Direction4::from_index(self as usize + 2)
+    }
+}
+
+impl TryFrom<char> for Direction4 {
+    type Error = String;
+
+    /// Parse arrows like `^`, and the letters `N`/`E`/`S`/`W` and `U`/`R`/`D`/`L`.
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'N' | 'U' => Ok(Direction4::North),
+            '>' | 'E' | 'R' => Ok(Direction4::East),
+            'v' | 'S' | 'D' => Ok(Direction4::South),
+            '<' | 'W' | 'L' => Ok(Direction4::West),
+            _ => Err(format!("`{c}` is not a direction")),
+        }
+    }
+}
+
+impl Mul<i64> for Direction4 {
+    type Output = Point2;
+
+    fn mul(self, factor: i64) -> Point2 {
+        self.offset() * factor
+    }
+}
+
+/// One of the four orthogonal and four diagonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// All directions, clockwise from north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    pub fn offset(self) -> Point2 {
+        match self {
+            Direction8::North => Point2::new(0, -1),
+            Direction8::NorthEast => Point2::new(1, -1),
+            Direction8::East => Point2::new(1, 0),
+            Direction8::SouthEast => Point2::new(1, 1),
+            Direction8::South => Point2::new(0, 1),
+            Direction8::SouthWest => Point2::new(-1, 1),
+            Direction8::West => Point2::new(-1, 0),
+            Direction8::NorthWest => Point2::new(-1, -1),
+        }
+    }
+
+    fn from_index(index: usize) -> Self {
+        Direction8::ALL[index % 8]
+    }
+
+    /// Turn clockwise by 45 degrees.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Direction8::from_index(self as usize + 1)
+    }
+
+    /// Turn counterclockwise by 45 degrees.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Direction8::from_index(self as usize + 7)
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        Direction8::from_index(self as usize + 4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        Direction8::from_index(direction as usize * 2)
+    }
+}
+
+impl Mul<i64> for Direction8 {
+    type Output = Point2;
+
+    fn mul(self, factor: i64) -> Point2 {
+        self.offset() * factor
+    }
+}
+
+/// The cells that count as neighbours.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The four orthogonally adjacent cells.
+    VonNeumann,
+    /// The eight orthogonally and diagonally adjacent cells.
+    Moore,
+}
+
+impl Neighbourhood {
+    /// Directions to the neighbours, clockwise from north.
+    pub fn directions(self) -> impl Iterator<Item = Direction8> {
+        Direction8::ALL
+            .into_iter()
+            .filter(move |d| self == Neighbourhood::Moore || !d.is_diagonal())
+    }
+
+    /// Positions of the neighbours of `position` within a grid of `rows` by `cols` cells, clockwise from the
+    /// one above.
+    pub fn positions(
+        self,
+        position: Position,
+        rows: usize,
+        cols: usize,
+    ) -> impl Iterator<Item = Position> {
+        let point = Point2::from_position(position);
+        self.directions()
+            .filter_map(move |d| (point + d).position_within(rows, cols))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction4, Direction8, Neighbourhood, Point2, Point3};
+
+    #[test]
+    fn computes_with_points() {
+        let a = Point2::new(3, -2);
+        let b = Point2::new(-1, 4);
+        assert_eq!(a + b, Point2::new(2, 2));
+        assert_eq!(a - b, Point2::new(4, -6));
+        assert_eq!(-a * 2, Point2::new(-6, 4));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+
+        assert_eq!(a.rotate_right().rotate_left(), a);
+        assert_eq!(a.rotate_right().rotate_right(), -a);
+        assert_eq!(
+            Direction4::North.offset().rotate_right(),
+            Direction4::East.offset()
+        );
+
+        let c = Point3::new(1, 2, 3);
+        assert_eq!(c - Point3::new(2, 2, 2) * 2, Point3::new(-3, -2, -1));
+        assert_eq!(c.manhattan(Point3::ORIGIN), 6);
+        assert_eq!(c.chebyshev(Point3::ORIGIN), 3);
+    }
+
+    #[test]
+    fn converts_positions() {
+        let point = Point2::from_position((2, 5));
+        assert_eq!(point, Point2::new(5, 2));
+        assert_eq!(point.position(), Some((2, 5)));
+        assert_eq!(point.position_within(3, 6), Some((2, 5)));
+        assert_eq!(point.position_within(2, 6), None);
+        assert_eq!(Point2::new(-1, 0).position(), None);
+    }
+
+    #[test]
+    fn lists_neighbours() {
+        let point = Point2::new(1, 1);
+        assert_eq!(point.neighbours4().count(), 4);
+        assert!(point.neighbours4().all(|n| n.manhattan(point) == 1));
+        assert!(point.neighbours8().all(|n| n.chebyshev(point) == 1));
+        assert_eq!(point.neighbours8().next(), Some(Point2::new(1, 0)));
+
+        let point = Point3::new(0, 0, 0);
+        assert!(point.neighbours6().all(|n| n.manhattan(point) == 1));
+        assert_eq!(point.neighbours26().count(), 26);
+        assert!(point.neighbours26().all(|n| n.chebyshev(point) == 1));
+    }
+
+    #[test]
+    fn turns_directions() {
+        for direction in Direction4::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(direction.opposite().offset(), -direction.offset());
+            assert_eq!(
+                direction.turn_right().offset(),
+                direction.offset().rotate_right()
+            );
+            assert_eq!(Direction8::from(direction).offset(), direction.offset());
+        }
+
+        for direction in Direction8::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.opposite().offset(), -direction.offset());
+            assert_eq!(
+                direction.is_diagonal(),
+                direction.offset().manhattan(Point2::ORIGIN) == 2
+            );
+        }
+
+        assert_eq!(Direction8::NorthWest.turn_right(), Direction8::North);
+        assert_eq!(Direction4::try_from('<'), Ok(Direction4::West));
+        assert!(Direction4::try_from('x').is_err());
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let positions: Vec<_> = Neighbourhood::Moore.positions((0, 0), 3, 3).collect();
+        assert_eq!(positions, [(0, 1), (1, 1), (1, 0)]);
+        let positions: Vec<_> = Neighbourhood::VonNeumann.positions((1, 2), 3, 3).collect();
+        assert_eq!(positions, [(0, 2), (2, 2), (1, 1)]);
+    }
+}
@@ -1,5 +1,17 @@
 use std::fmt::Debug;
 
+// lets derive macros refer to `::advent_of_code` from within this crate, too.
+extern crate self as advent_of_code;
+
+pub mod automaton;
+pub mod bit_grid;
+pub mod digits;
+pub mod geometry;
+pub mod modular;
+pub mod parse;
+pub mod search;
+pub mod sparse_grid;
+pub mod subsequence;
 pub mod template;
 
 pub enum Endpoint {
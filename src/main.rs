@@ -1,4 +1,4 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, compare, download, read, scaffold, solve, time};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
@@ -7,8 +7,8 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{BenchConfig, BuildOptions, Day, ExportFormat};
+    use std::{path::PathBuf, process};
 
     pub enum AppArguments {
         Download {
@@ -16,6 +16,7 @@ mod args {
         },
         Read {
             day: Day,
+            part: Option<u8>,
         },
         Scaffold {
             day: Day,
@@ -24,9 +25,12 @@ mod args {
         },
         Solve {
             day: Day,
-            release: bool,
-            dhat: bool,
+            options: BuildOptions,
             submit: Option<u8>,
+            yes: bool,
+            dry_run: bool,
+            watch: bool,
+            test: bool,
         },
         All {
             release: bool,
@@ -35,11 +39,31 @@ mod args {
             all: bool,
             day: Option<Day>,
             store: bool,
+            history: bool,
+            export: Option<(ExportFormat, PathBuf)>,
+            options: BuildOptions,
+            bench: BenchConfig,
+        },
+        Compare {
+            day: Option<Day>,
+            rev: String,
+            rounds: usize,
+            options: BuildOptions,
+            bench: BenchConfig,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    fn parse_build_options(args: &mut pico_args::Arguments) -> BuildOptions {
+        BuildOptions {
+            release: args.contains("--release"),
+            dhat: args.contains("--dhat"),
+            count_allocations: args.contains("--count-allocations"),
+            cpu_time: args.contains("--cpu-time"),
+        }
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
@@ -50,17 +74,65 @@ mod args {
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let history = args.contains("--history");
+                let options = parse_build_options(&mut args);
+                let bench = BenchConfig::parse(&mut args)?;
+                let compare: Option<String> = args.opt_value_from_str("--compare")?;
+                let rounds: Option<usize> = args.opt_value_from_str("--rounds")?;
+                let export_format: Option<ExportFormat> = args.opt_value_from_str("--export")?;
+
+                let mut free: Vec<String> = vec![];
+                while let Some(arg) = args.opt_free_from_str()? {
+                    free.push(arg);
+                }
+
+                let export = match export_format {
+                    Some(format) => {
+                        // the export path and an optional day can come in any order.
+                        let path_index = free
+                            .iter()
+                            .position(|x| free.len() == 1 || x.parse::<Day>().is_err());
+
+                        match path_index {
+                            Some(i) => Some((format, PathBuf::from(free.remove(i)))),
+                            None => {
+                                eprintln!(
+                                    "Missing export path. Format: cargo time --export csv <path>"
+                                );
+                                process::exit(1);
+                            }
+                        }
+                    }
+                    None => None,
+                };
+
+                let day = free.first().map(|x| x.parse()).transpose()?;
+
+                if let Some(rev) = compare {
+                    return Ok(AppArguments::Compare {
+                        day,
+                        rev,
+                        rounds: rounds.unwrap_or(3),
+                        options,
+                        bench,
+                    });
+                }
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    day,
                     store,
+                    history,
+                    export,
+                    options,
+                    bench,
                 }
             }
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                part: args.opt_value_from_str("--part")?,
                 day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
@@ -70,9 +142,12 @@ mod args {
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
+                options: parse_build_options(&mut args),
                 submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                yes: args.contains("--yes"),
+                dry_run: args.contains("--dry-run"),
+                watch: args.contains("--watch"),
+                test: args.contains("--test"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -103,9 +178,24 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                history,
+                export,
+                options,
+                bench,
+            } => time::handle(day, all, store, history, export, options, bench),
+            AppArguments::Compare {
+                day,
+                rev,
+                rounds,
+                options,
+                bench,
+            } => compare::handle(day, &rev, rounds, options, bench),
             AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::Read { day, part } => read::handle(day, part),
             AppArguments::Scaffold {
                 day,
                 download,
@@ -118,17 +208,20 @@ fn main() {
             }
             AppArguments::Solve {
                 day,
-                release,
-                dhat,
+                options,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                yes,
+                dry_run,
+                watch,
+                test,
+            } => solve::handle(day, options, submit, yes, dry_run, watch, test),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
                         scaffold::handle(day, false);
                         download::handle(day);
-                        read::handle(day)
+                        read::handle(day, None)
                     }
                     None => {
                         eprintln!(
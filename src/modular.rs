@@ -0,0 +1,323 @@
+//! Arithmetic modulo `n`, for dials, rings and other positions that wrap around.
+//!
+//! [`Cyclic`] is a value in `0..N` that can be rotated in both directions and can count how often a rotation
+//! passes a position. The free functions work with moduli that are only known at runtime.
+//!
+//! ```
+//! use advent_of_code::modular::{self, Cyclic};
+//!
+//! let mut dial = Cyclic::<100>::new(50);
+//! assert_eq!(dial.passes(-168, 0), 2);
+//! dial.rotate(-168);
+//! assert_eq!(dial.value(), 82);
+//!
+//! assert_eq!(modular::crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+//! ```
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Greatest common divisor `g` of `a` and `b`, with `x` and `y` such that `a * x + b * y = g`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// `base^exp mod modulus`, by repeated squaring.
+///
+/// # Panics
+/// Panics if `modulus` is zero.
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    let result = result as u64;
+    result
+}
+
+/// The `x` with `a * x ≡ 1 (mod modulus)`, if `a` and `modulus` are coprime.
+///
+/// # Panics
+/// Panics if `modulus` is zero.
+pub fn mod_inverse(a: u64, modulus: u64) -> Option<u64> {
+    assert!(modulus > 0, "modulus must be positive");
+
+    let (g, x, _) = extended_gcd(i128::from(a % modulus), i128::from(modulus));
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    (g == 1).then(|| x.rem_euclid(i128::from(modulus)) as u64)
+}
+
+/// Solve a system of congruences `x ≡ residue (mod modulus)` with the chinese remainder theorem.
+/// Returns the smallest non-negative solution and the modulus of all solutions, i.e. the least common
+/// multiple of the moduli. Moduli don't have to be coprime, but `None` is returned when the congruences
+/// contradict each other or the combined modulus exceeds `u64`.
+///
+/// # Panics
+/// Panics if a modulus is zero.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let mut result: (i128, i128) = (0, 1);
+
+    for &(residue, modulus) in congruences {
+        assert!(modulus > 0, "modulus must be positive");
+
+        let (r1, m1) = result;
+        let (r2, m2) = (i128::from(residue), i128::from(modulus));
+        let (g, x, _) = extended_gcd(m1, m2);
+
+        if (r2 - r1) % g != 0 {
+            return None;
+        }
+
+        let lcm = m1 / g * m2;
+        if lcm > i128::from(u64::MAX) {
+            return None;
+        }
+
+        // `x` inverts `m1 / g` modulo `m2 / g`, so this step moves `r1` onto `r2` in multiples of `m1`.
+        let step = ((r2 - r1) / g % (m2 / g)) * x % (m2 / g);
+        result = ((r1 + m1 * step).rem_euclid(lcm), lcm);
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some((result.0 as u64, result.1 as u64))
+}
+
+/// A value in `0..N` that wraps around, like the position on a dial with `N` ticks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cyclic<const N: u64>(u64);
+
+/// [`Cyclic`], for when the value is used as a number in `Z/NZ` rather than a position.
+pub type ModInt<const N: u64> = Cyclic<N>;
+
+impl<const N: u64> Cyclic<N> {
+    pub const MODULUS: u64 = N;
+
+    /// Wrap `value` into `0..N`, counting negative values backwards from `N`.
+    pub fn new(value: i64) -> Self {
+        const { assert!(N > 0, "modulus must be positive") };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Cyclic(i128::from(value).rem_euclid(i128::from(N)) as u64)
+    }
+
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    /// The value after turning `steps` ticks forward, or backward for negative `steps`.
+    #[must_use]
+    pub fn rotated(self, steps: i64) -> Self {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Cyclic((i128::from(self.0) + i128::from(steps)).rem_euclid(i128::from(N)) as u64)
+    }
+
+    /// Turn `steps` ticks forward, or backward for negative `steps`.
+    pub fn rotate(&mut self, steps: i64) {
+        *self = self.rotated(steps);
+    }
+
+    /// How often turning `steps` ticks lands on or passes `target`. Every tick is one step, so the current
+    /// value only counts once a full turn returns to it.
+    pub fn passes(self, steps: i64, target: u64) -> u64 {
+        let target = Cyclic::<N>::from(target);
+
+        // ticks until the first visit of `target` in the direction of the rotation.
+        let first = if steps >= 0 {
+            (target - self).0
+        } else {
+            (self - target).0
+        };
+        let first = if first == 0 { N } else { first };
+
+        let steps = steps.unsigned_abs();
+        if steps < first {
+            0
+        } else {
+            (steps - first) / N + 1
+        }
+    }
+
+    /// `self^exp`.
+    #[must_use]
+    pub fn pow(self, exp: u64) -> Self {
+        Cyclic(mod_pow(self.0, exp, N))
+    }
+
+    /// The multiplicative inverse, if the value is coprime to `N`.
+    pub fn inverse(self) -> Option<Self> {
+        mod_inverse(self.0, N).map(Cyclic)
+    }
+}
+
+impl<const N: u64> From<u64> for Cyclic<N> {
+    fn from(value: u64) -> Self {
+        const { assert!(N > 0, "modulus must be positive") };
+        Cyclic(value % N)
+    }
+}
+
+impl<const N: u64> From<Cyclic<N>> for u64 {
+    fn from(value: Cyclic<N>) -> Self {
+        value.0
+    }
+}
+
+impl<const N: u64> Display for Cyclic<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const N: u64> Add for Cyclic<N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        #[allow(clippy::cast_possible_truncation)]
+        Cyclic(((u128::from(self.0) + u128::from(other.0)) % u128::from(N)) as u64)
+    }
+}
+
+impl<const N: u64> Sub for Cyclic<N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<const N: u64> Neg for Cyclic<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Cyclic((N - self.0) % N)
+    }
+}
+
+impl<const N: u64> Mul for Cyclic<N> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        #[allow(clippy::cast_possible_truncation)]
+        Cyclic((u128::from(self.0) * u128::from(other.0) % u128::from(N)) as u64)
+    }
+}
+
+impl<const N: u64> AddAssign for Cyclic<N> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const N: u64> SubAssign for Cyclic<N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const N: u64> MulAssign for Cyclic<N> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cyclic, ModInt, crt, mod_inverse, mod_pow};
+
+    /// Rotate one tick at a time, counting the visits of `target`.
+    fn step_by_step<const N: u64>(start: Cyclic<N>, steps: i64, target: u64) -> (Cyclic<N>, u64) {
+        let mut current = start;
+        let mut visits = 0;
+
+        for _ in 0..steps.unsigned_abs() {
+            current = current.rotated(steps.signum());
+            if current.value() == target {
+                visits += 1;
+            }
+        }
+
+        (current, visits)
+    }
+
+    fn check_against_stepper<const N: u64>() {
+        for start in 0..N {
+            let start = Cyclic::<N>::from(start);
+            for steps in -3 * 100..=3 * 100 {
+                for target in [0, N / 2, N - 1] {
+                    let (end, visits) = step_by_step(start, steps, target);
+                    assert_eq!(start.rotated(steps), end, "{start} by {steps}");
+                    assert_eq!(
+                        start.passes(steps, target),
+                        visits,
+                        "{start} by {steps} passing {target}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rotates_like_a_stepper() {
+        check_against_stepper::<1>();
+        check_against_stepper::<7>();
+        check_against_stepper::<100>();
+    }
+
+    #[test]
+    fn wraps_values() {
+        assert_eq!(Cyclic::<100>::new(-1).value(), 99);
+        assert_eq!(Cyclic::<100>::new(250).value(), 50);
+        assert_eq!(Cyclic::<7>::from(3) - Cyclic::from(5), Cyclic::from(5));
+        assert_eq!(-Cyclic::<7>::from(0), Cyclic::from(0));
+        assert_eq!(
+            Cyclic::<{ u64::MAX }>::from(u64::MAX - 1) * Cyclic::from(2),
+            Cyclic::from(u64::MAX - 2)
+        );
+    }
+
+    #[test]
+    fn computes_powers_and_inverses() {
+        for a in 1..13 {
+            let a = Cyclic::<13>::from(a);
+            assert_eq!(a * a.inverse().unwrap(), Cyclic::from(1));
+            assert_eq!(a.pow(12), Cyclic::from(1));
+            assert_eq!(a.pow(3), a * a * a);
+        }
+
+        assert_eq!(ModInt::<12>::from(4).inverse(), None);
+        assert_eq!(mod_pow(2, 0, 1), 0);
+        assert_eq!(mod_pow(3, 200, 1_000_000_007), 136_318_165);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+    }
+
+    #[test]
+    fn solves_congruences() {
+        for moduli in [[3, 5, 7], [4, 6, 9], [12, 18, 8]] {
+            let lcm = moduli.iter().fold(1, |lcm, &m| lcm / gcd(lcm, m) * m);
+            for x in 0..lcm {
+                let congruences = moduli.map(|m| (x % m, m));
+                assert_eq!(crt(&congruences), Some((x, lcm)), "{congruences:?}");
+            }
+        }
+
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+}
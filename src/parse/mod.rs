@@ -0,0 +1,518 @@
+//! Helpers for parsing puzzle inputs.
+//!
+//! The helpers work on borrowed slices of the input and report errors with the line and column they
+//! occurred at, so that malformed inputs can be debugged without a `dbg!` session.
+//!
+//! ```
+//! use advent_of_code::parse;
+//!
+//! let input = "seeds: 79 14 55\n\nsoil: -1 2\nwater: 3 4\n";
+//! let mut sections = parse::sections(input);
+//!
+//! let (_, seeds) = sections.next().unwrap().lines().next().unwrap().key_value(":")?;
+//! assert_eq!(seeds.numbers::<u32>().collect::<Result<Vec<_>, _>>()?, [79, 14, 55]);
+//!
+//! for line in sections.next().unwrap().lines() {
+//!     let (name, values) = line.key_value(":")?;
+//!     let values: Vec<i32> = values.numbers().collect::<Result<_, _>>()?;
+//! }
+//! # Ok::<(), parse::ParseError>(())
+//! ```
+use std::fmt::Display;
+
+pub mod pattern;
+
+/// Error returned by the parsing helpers.
+/// Lines and columns are 1-based, columns count bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Expected a digit, found another character or the end of the line (`None`).
+    ExpectedDigit(Option<char>),
+    /// The number does not fit into the target type.
+    Overflow,
+    /// The line was expected to end, found another character.
+    TrailingCharacters(char),
+    /// The separator was not found in the line.
+    MissingSeparator(String),
+    /// The text of a pattern was not found at this position.
+    ExpectedText(String),
+    /// Expected a character, found the end of the line.
+    ExpectedCharacter,
+    /// Error raised by a solution via [`Line::error`].
+    Custom(String),
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorKind::ExpectedDigit(Some(c)) => write!(f, "expected a digit, found `{c}`"),
+            ErrorKind::ExpectedDigit(None) => write!(f, "expected a digit, found end of line"),
+            ErrorKind::Overflow => write!(f, "number too large for the target type"),
+            ErrorKind::TrailingCharacters(c) => {
+                write!(f, "expected end of line, found `{c}`")
+            }
+            ErrorKind::MissingSeparator(sep) => write!(f, "expected separator `{sep}`"),
+            ErrorKind::ExpectedText(text) => write!(f, "expected `{text}`"),
+            ErrorKind::ExpectedCharacter => write!(f, "expected a character, found end of line"),
+            ErrorKind::Custom(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/* -------------------------------------------------------------------------- */
+
+/// Primitive integers that can be parsed by the helpers in this module.
+pub trait Integer: Copy {
+    const SIGNED: bool;
+
+    /// Returns `self * 10 + digit`, or `self * 10 - digit` for negative numbers, if it does not overflow.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+
+    fn zero() -> Self;
+}
+
+macro_rules! impl_integer {
+    ($signed:literal => $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const SIGNED: bool = $signed;
+
+                #[inline]
+                #[allow(clippy::cast_possible_wrap, clippy::cast_lossless)]
+                fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                    let shifted = self.checked_mul(10)?;
+                    if negative {
+                        shifted.checked_sub(digit as $t)
+                    } else {
+                        shifted.checked_add(digit as $t)
+                    }
+                }
+
+                #[inline]
+                fn zero() -> Self {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(false => u8, u16, u32, u64, u128, usize);
+impl_integer!(true => i8, i16, i32, i64, i128, isize);
+
+/// Parse the digits at the start of `bytes`. Returns the number and the count of consumed bytes.
+/// Errors always refer to the start of `bytes`.
+#[inline]
+fn parse_digits<T: Integer>(bytes: &[u8], negative: bool) -> Result<(T, usize), ErrorKind> {
+    let len = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+
+    if len == 0 {
+        return Err(ErrorKind::ExpectedDigit(
+            bytes.first().map(|b| char::from(*b)),
+        ));
+    }
+
+    let mut value = T::zero();
+    for &b in &bytes[..len] {
+        value = value
+            .push_digit(b - b'0', negative)
+            .ok_or(ErrorKind::Overflow)?;
+    }
+
+    Ok((value, len))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A line of the input, or a part of it, that knows its position for error reporting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'a> {
+    text: &'a str,
+    number: usize,
+    column: usize,
+}
+
+impl<'a> Line<'a> {
+    /// Wrap `text` that starts at `column` of line `number`, both 1-based.
+    pub fn new(text: &'a str, number: usize, column: usize) -> Self {
+        Line {
+            text,
+            number,
+            column,
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// 1-based number of the line in the input.
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    /// 1-based column this part of the line starts at.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Create an error at byte `offset` of this part of the line.
+    pub fn error(&self, offset: usize, kind: ErrorKind) -> ParseError {
+        ParseError {
+            line: self.number,
+            column: self.column + offset,
+            kind,
+        }
+    }
+
+    /// The subslice `start..end` of this line.
+    fn slice(&self, start: usize, end: usize) -> Line<'a> {
+        Line::new(&self.text[start..end], self.number, self.column + start)
+    }
+
+    /// Remove leading and trailing whitespace.
+    pub fn trim(&self) -> Line<'a> {
+        let start = self.text.len() - self.text.trim_start().len();
+        let end = self.text.trim_end().len().max(start);
+        self.slice(start, end)
+    }
+
+    /// Parse the whole line as an unsigned integer.
+    pub fn unsigned<T: Integer>(&self) -> Result<T, ParseError> {
+        self.integer(false)
+    }
+
+    /// Parse the whole line as an integer with an optional `+` or `-` sign.
+    pub fn signed<T: Integer>(&self) -> Result<T, ParseError> {
+        self.integer(true)
+    }
+
+    fn integer<T: Integer>(&self, allow_sign: bool) -> Result<T, ParseError> {
+        let bytes = self.text.as_bytes();
+
+        let (negative, start) = match bytes.first() {
+            Some(b'-') if allow_sign && T::SIGNED => (true, 1),
+            Some(b'+') if allow_sign => (false, 1),
+            _ => (false, 0),
+        };
+
+        let (value, len) =
+            parse_digits(&bytes[start..], negative).map_err(|kind| self.error(start, kind))?;
+
+        match self.text[start + len..].chars().next() {
+            Some(c) => Err(self.error(start + len, ErrorKind::TrailingCharacters(c))),
+            None => Ok(value),
+        }
+    }
+
+    /// Iterate over all integers in the line, skipping any other characters.
+    /// For signed types, a `-` directly before a number is read as its sign, unless it follows
+    /// a letter or digit (as in ranges like `10-20`).
+    pub fn numbers<T: Integer>(&self) -> Numbers<'a, T> {
+        Numbers {
+            line: *self,
+            offset: 0,
+            marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Split the line at the first occurrence of `separator`.
+    pub fn split_once(&self, separator: &str) -> Result<(Line<'a>, Line<'a>), ParseError> {
+        let index = self.text.find(separator).ok_or_else(|| {
+            self.error(
+                self.text.len(),
+                ErrorKind::MissingSeparator(separator.into()),
+            )
+        })?;
+
+        Ok((
+            self.slice(0, index),
+            self.slice(index + separator.len(), self.text.len()),
+        ))
+    }
+
+    /// Split a `key<separator>value` pair, trimming whitespace around both.
+    pub fn key_value(&self, separator: &str) -> Result<(Line<'a>, Line<'a>), ParseError> {
+        let (key, value) = self.split_once(separator)?;
+        Ok((key.trim(), value.trim()))
+    }
+
+    /// Iterate over the parts of the line separated by `separator`.
+    pub fn split(&self, separator: &'a str) -> impl Iterator<Item = Line<'a>> + 'a {
+        let line = *self;
+        let mut start = 0;
+
+        self.text
+            .match_indices(separator)
+            .map(|(index, _)| index)
+            .chain(std::iter::once(self.text.len()))
+            .map(move |end| {
+                let part = line.slice(start, end);
+                start = end + separator.len();
+                part
+            })
+    }
+
+    /// Iterate over fixed-width columns of `width` characters. The last column may be shorter.
+    ///
+    /// # Panics
+    /// Panics if `width` is zero.
+    pub fn columns(&self, width: usize) -> impl Iterator<Item = Line<'a>> + 'a {
+        assert!(width > 0, "column width must be positive");
+        let line = *self;
+        let mut starts = self
+            .text
+            .char_indices()
+            .map(|(index, _)| index)
+            .step_by(width)
+            .peekable();
+
+        std::iter::from_fn(move || {
+            let start = starts.next()?;
+            let end = starts.peek().copied().unwrap_or(line.text.len());
+            Some(line.slice(start, end))
+        })
+    }
+}
+
+impl Display for Line<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// Iterator over the integers in a [`Line`], see [`Line::numbers`].
+pub struct Numbers<'a, T> {
+    line: Line<'a>,
+    offset: usize,
+    marker: std::marker::PhantomData<T>,
+}
+
+impl<T: Integer> Iterator for Numbers<'_, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.line.text.as_bytes();
+        let start = self.offset + bytes[self.offset..].iter().position(u8::is_ascii_digit)?;
+
+        let negative = T::SIGNED
+            && start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric());
+
+        let result = parse_digits(&bytes[start..], negative);
+
+        // skip the remaining digits of a number that overflowed.
+        self.offset = start
+            + bytes[start..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+
+        Some(match result {
+            Ok((value, _)) => Ok(value),
+            Err(kind) => Err(self.line.error(start - usize::from(negative), kind)),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Iterate over the lines of `input`, numbered from 1.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    lines_from(input, 1)
+}
+
+fn lines_from(input: &str, first: usize) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(move |(i, text)| Line::new(text, first + i, 1))
+}
+
+/// A block of lines, separated from other blocks by blank lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Section<'a> {
+    text: &'a str,
+    first_line: usize,
+}
+
+impl<'a> Section<'a> {
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// 1-based number of the first line of the section in the input.
+    pub fn first_line(&self) -> usize {
+        self.first_line
+    }
+
+    /// Iterate over the lines of the section, numbered by their position in the input.
+    pub fn lines(&self) -> impl Iterator<Item = Line<'a>> + 'a {
+        lines_from(self.text, self.first_line)
+    }
+}
+
+/// Iterate over the blank-line-separated sections of `input`.
+pub fn sections(input: &str) -> impl Iterator<Item = Section<'_>> {
+    let mut offset = 0;
+    let mut number = 0;
+    let mut current: Option<(usize, usize, usize)> = None;
+
+    let mut lines = input.split_inclusive('\n');
+
+    std::iter::from_fn(move || {
+        loop {
+            let Some(line) = lines.next() else {
+                return current.take().map(|(start, end, first_line)| Section {
+                    text: &input[start..end],
+                    first_line,
+                });
+            };
+
+            let start = offset;
+            offset += line.len();
+            number += 1;
+
+            if line.trim().is_empty() {
+                if let Some((start, end, first_line)) = current.take() {
+                    return Some(Section {
+                        text: &input[start..end],
+                        first_line,
+                    });
+                }
+            } else {
+                let end = start + line.trim_end_matches(['\n', '\r']).len();
+                current = Some(match current {
+                    Some((section_start, _, first_line)) => (section_start, end, first_line),
+                    None => (start, end, number),
+                });
+            }
+        }
+    })
+}
+
+/// Parse `s` as an unsigned integer.
+pub fn unsigned<T: Integer>(s: &str) -> Result<T, ParseError> {
+    Line::new(s, 1, 1).unsigned()
+}
+
+/// Parse `s` as an integer with an optional sign.
+pub fn signed<T: Integer>(s: &str) -> Result<T, ParseError> {
+    Line::new(s, 1, 1).signed()
+}
+
+/// Collect all integers in `s`, see [`Line::numbers`].
+pub fn numbers<T: Integer>(s: &str) -> Result<Vec<T>, ParseError> {
+    Line::new(s, 1, 1).numbers().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorKind, Line, ParseError, lines, numbers, sections, signed, unsigned};
+
+    #[test]
+    fn parses_integers() {
+        assert_eq!(unsigned::<u8>("255"), Ok(255));
+        assert_eq!(signed::<i8>("-128"), Ok(-128));
+        assert_eq!(signed::<i64>("+42"), Ok(42));
+        assert_eq!(unsigned::<u8>("256").unwrap_err().kind, ErrorKind::Overflow);
+        assert_eq!(
+            unsigned::<i32>("-1").unwrap_err(),
+            ParseError {
+                line: 1,
+                column: 1,
+                kind: ErrorKind::ExpectedDigit(Some('-'))
+            }
+        );
+        assert_eq!(
+            unsigned::<u32>("12a").unwrap_err().to_string(),
+            "line 1, column 3: expected end of line, found `a`"
+        );
+        assert_eq!(
+            unsigned::<u32>("").unwrap_err().kind,
+            ErrorKind::ExpectedDigit(None)
+        );
+    }
+
+    #[test]
+    fn extracts_numbers() {
+        assert_eq!(
+            numbers::<i32>("p=0,-4 v=3,-3 x-5 10-20"),
+            Ok(vec![0, -4, 3, -3, 5, 10, 20])
+        );
+        assert_eq!(numbers::<u32>("Game 12: -3 red"), Ok(vec![12, 3]));
+        assert_eq!(numbers::<u8>("no numbers"), Ok(vec![]));
+
+        let error = numbers::<u8>("1 300 2").unwrap_err();
+        assert_eq!((error.column, error.kind), (3, ErrorKind::Overflow));
+    }
+
+    #[test]
+    fn reports_positions_of_nested_parts() {
+        let input = "a: 1\nb: 2, x\n";
+        let line = lines(input).nth(1).unwrap();
+        let (key, value) = line.key_value(":").unwrap();
+        assert_eq!(key.as_str(), "b");
+
+        let parts: Vec<Line> = value.split(", ").collect();
+        assert_eq!(parts[0].unsigned::<u32>(), Ok(2));
+        assert_eq!(
+            parts[1].unsigned::<u32>().unwrap_err().to_string(),
+            "line 2, column 7: expected a digit, found `x`"
+        );
+        assert_eq!(
+            line.split_once("=").unwrap_err().to_string(),
+            "line 2, column 8: expected separator `=`"
+        );
+    }
+
+    #[test]
+    fn splits_columns() {
+        let line = Line::new("[A] [B]     [C]", 1, 1);
+        let columns: Vec<&str> = line.columns(4).map(|c| c.trim().as_str()).collect();
+        assert_eq!(columns, ["[A]", "[B]", "", "[C]"]);
+        assert_eq!(line.columns(4).nth(3).unwrap().column(), 13);
+
+        let line = Line::new("é.ü#ß", 1, 1);
+        let columns: Vec<&str> = line.columns(2).map(|c| c.as_str()).collect();
+        assert_eq!(columns, ["é.", "ü#", "ß"]);
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "\n1\n2\n\n\n3\r\n4\r\n\r\n5";
+        let sections: Vec<_> = sections(input).collect();
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].as_str(), "1\n2");
+        assert_eq!(sections[0].first_line(), 2);
+        assert_eq!(sections[1].as_str(), "3\r\n4");
+
+        let numbers: Vec<usize> = sections[1].lines().map(|l| l.number()).collect();
+        assert_eq!(numbers, [6, 7]);
+        assert_eq!(sections[2].first_line(), 9);
+    }
+}
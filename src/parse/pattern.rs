@@ -0,0 +1,531 @@
+//! Declarative line patterns like `{dir:char}{steps:i64}` or `{start:u64}-{end:u64}`.
+//!
+//! Placeholders are written as `{name:type}`. Both the name and the type are optional, `{}` works as well;
+//! use `{{` and `}}` for literal braces. How much of a line a placeholder consumes depends on the type it is
+//! parsed into: a `char` takes a single character, integers take an optional sign and all following digits,
+//! and strings take everything up to the next literal text of the pattern (or the end of the line).
+//!
+//! Lines can be parsed into tuples, or into structs that derive [`FromPattern`]:
+//!
+//! ```
+//! use advent_of_code::parse::pattern::{self, FromPattern, HasPattern};
+//!
+//! let ranges: Vec<(u64, u64)> = pattern::parse_lines("{}-{}", "3-5\n10-14\n")?;
+//!
+//! #[derive(FromPattern)]
+//! #[pattern("{dir:char}{steps:i64}")]
+//! struct Step {
+//!     dir: char,
+//!     steps: i64,
+//! }
+//!
+//! let steps = Step::parse_lines("L68\nR48\n")?;
+//! let step: Step = "L5".parse()?;
+//! # Ok::<(), advent_of_code::parse::ParseError>(())
+//! ```
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use crate::parse::{ErrorKind, Line, ParseError, lines};
+
+pub use advent_of_code_derive::FromPattern;
+
+/// How much of a line a placeholder consumes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Extent {
+    /// A single character.
+    Char,
+    /// An optional sign, if signed, followed by digits.
+    Integer { signed: bool },
+    /// Everything up to the next literal text of the pattern, or the end of the line.
+    Text,
+}
+
+/// Types a single placeholder can be parsed into.
+pub trait Field<'a>: Sized {
+    const EXTENT: Extent;
+    /// Name of the type in placeholders, e.g. `u64` in `{n:u64}`.
+    const TYPE_NAME: &'static str;
+
+    fn parse_field(part: Line<'a>) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_integer_field {
+    ($signed:literal, $method:ident => $($t:ty),*) => {
+        $(
+            impl<'a> Field<'a> for $t {
+                const EXTENT: Extent = Extent::Integer { signed: $signed };
+                const TYPE_NAME: &'static str = stringify!($t);
+
+                fn parse_field(part: Line<'a>) -> Result<Self, ParseError> {
+                    part.$method()
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_field!(false, unsigned => u8, u16, u32, u64, u128, usize);
+impl_integer_field!(true, signed => i8, i16, i32, i64, i128, isize);
+
+impl<'a> Field<'a> for char {
+    const EXTENT: Extent = Extent::Char;
+    const TYPE_NAME: &'static str = "char";
+
+    fn parse_field(part: Line<'a>) -> Result<Self, ParseError> {
+        part.as_str()
+            .chars()
+            .next()
+            .ok_or_else(|| part.error(0, ErrorKind::ExpectedCharacter))
+    }
+}
+
+impl<'a> Field<'a> for &'a str {
+    const EXTENT: Extent = Extent::Text;
+    const TYPE_NAME: &'static str = "str";
+
+    fn parse_field(part: Line<'a>) -> Result<Self, ParseError> {
+        Ok(part.as_str())
+    }
+}
+
+impl<'a> Field<'a> for String {
+    const EXTENT: Extent = Extent::Text;
+    const TYPE_NAME: &'static str = "String";
+
+    fn parse_field(part: Line<'a>) -> Result<Self, ParseError> {
+        Ok(part.as_str().to_string())
+    }
+}
+
+/// The raw part of the line, for fields that need further parsing.
+impl<'a> Field<'a> for Line<'a> {
+    const EXTENT: Extent = Extent::Text;
+    const TYPE_NAME: &'static str = "Line";
+
+    fn parse_field(part: Line<'a>) -> Result<Self, ParseError> {
+        Ok(part)
+    }
+}
+
+/// Types a whole line can be parsed into, with one field per placeholder.
+pub trait FromPattern<'a>: Sized {
+    /// Extent and type name of every field, in the order of the placeholders.
+    const FIELDS: &'static [(Extent, &'static str)];
+
+    /// Build the value from the parts of the line matched by each placeholder.
+    fn from_fields(fields: &[Line<'a>]) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_tuple {
+    ($($t:ident $i:tt),+) => {
+        impl<'a, $($t: Field<'a>),+> FromPattern<'a> for ($($t,)+) {
+            const FIELDS: &'static [(Extent, &'static str)] = &[$(($t::EXTENT, $t::TYPE_NAME)),+];
+
+            fn from_fields(fields: &[Line<'a>]) -> Result<Self, ParseError> {
+                Ok(($($t::parse_field(fields[$i])?,)+))
+            }
+        }
+    };
+}
+
+impl_tuple!(A 0);
+impl_tuple!(A 0, B 1);
+impl_tuple!(A 0, B 1, C 2);
+impl_tuple!(A 0, B 1, C 2, D 3);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+
+/// Types with a pattern of their own, implemented by `#[derive(FromPattern)]`.
+pub trait HasPattern<'a>: FromPattern<'a> {
+    fn pattern() -> &'static Pattern;
+
+    fn parse_line(line: Line<'a>) -> Result<Self, ParseError> {
+        Self::pattern().parse(line)
+    }
+
+    /// Parse every line of `input`.
+    fn parse_lines(input: &'a str) -> Result<Vec<Self>, ParseError> {
+        let mut parser = Self::pattern().parser();
+        lines(input).map(|line| parser.parse(line)).collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Placeholder {
+        name: Option<String>,
+        type_name: Option<String>,
+    },
+}
+
+/// A compiled line pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+/// Error for malformed patterns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternError(String);
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+impl Pattern {
+    /// Compile `pattern`. Fails for unbalanced braces.
+    pub fn new(pattern: &str) -> Result<Self, PatternError> {
+        let mut segments = vec![];
+        let mut literal = String::new();
+        let mut chars = pattern.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some('{') | None => {
+                                return Err(PatternError(format!(
+                                    "unclosed placeholder in `{pattern}`"
+                                )));
+                            }
+                            Some(c) => placeholder.push(c),
+                        }
+                    }
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+
+                    let (name, type_name) = match placeholder.split_once(':') {
+                        Some((name, type_name)) => (name.trim(), Some(type_name.trim())),
+                        None => (placeholder.trim(), None),
+                    };
+
+                    segments.push(Segment::Placeholder {
+                        name: Some(name).filter(|x| !x.is_empty()).map(String::from),
+                        type_name: type_name.filter(|x| !x.is_empty()).map(String::from),
+                    });
+                }
+                '}' => {
+                    return Err(PatternError(format!(
+                        "unmatched `}}` in `{pattern}`, use `}}}}` for a literal brace"
+                    )));
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Pattern {
+            source: pattern.into(),
+            segments,
+        })
+    }
+
+    /// Names of the placeholders, in order.
+    pub fn names(&self) -> impl Iterator<Item = Option<&str>> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Placeholder { name, .. } => Some(name.as_deref()),
+            Segment::Literal(_) => None,
+        })
+    }
+
+    fn type_names(&self) -> impl Iterator<Item = Option<&str>> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Placeholder { type_name, .. } => Some(type_name.as_deref()),
+            Segment::Literal(_) => None,
+        })
+    }
+
+    /// Split `line` into the parts matched by each placeholder, consuming `extents` of the line. The parts
+    /// replace the contents of `fields`, so that the buffer can be reused for many lines.
+    pub fn split<'a>(
+        &self,
+        line: Line<'a>,
+        extents: &[Extent],
+        fields: &mut Vec<Line<'a>>,
+    ) -> Result<(), ParseError> {
+        self.split_with(line, |i| extents[i], fields)
+    }
+
+    fn split_with<'a>(
+        &self,
+        line: Line<'a>,
+        extent: impl Fn(usize) -> Extent,
+        fields: &mut Vec<Line<'a>>,
+    ) -> Result<(), ParseError> {
+        let text = line.as_str();
+        let mut offset = 0;
+        fields.clear();
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            let rest = &text[offset..];
+
+            let len = match segment {
+                Segment::Literal(literal) => {
+                    if !rest.starts_with(literal.as_str()) {
+                        return Err(line.error(offset, ErrorKind::ExpectedText(literal.clone())));
+                    }
+                    offset += literal.len();
+                    continue;
+                }
+                Segment::Placeholder { .. } => match extent(fields.len()) {
+                    Extent::Char => rest
+                        .chars()
+                        .next()
+                        .map(char::len_utf8)
+                        .ok_or_else(|| line.error(offset, ErrorKind::ExpectedCharacter))?,
+                    Extent::Integer { signed } => {
+                        let sign = usize::from(signed && rest.starts_with(['-', '+']));
+                        let digits = rest[sign..].bytes().take_while(u8::is_ascii_digit).count();
+                        if digits == 0 {
+                            let found = rest[sign..].chars().next();
+                            return Err(line.error(offset + sign, ErrorKind::ExpectedDigit(found)));
+                        }
+                        sign + digits
+                    }
+                    Extent::Text => match self.segments.get(i + 1) {
+                        Some(Segment::Literal(next)) => {
+                            rest.find(next.as_str()).ok_or_else(|| {
+                                line.error(text.len(), ErrorKind::MissingSeparator(next.clone()))
+                            })?
+                        }
+                        _ => rest.len(),
+                    },
+                },
+            };
+
+            fields.push(line.slice(offset, offset + len));
+            offset += len;
+        }
+
+        match text[offset..].chars().next() {
+            Some(c) => Err(line.error(offset, ErrorKind::TrailingCharacters(c))),
+            None => Ok(()),
+        }
+    }
+
+    /// Parse `line` into `T`. To parse many lines, check the types once with [`Pattern::parser`] instead.
+    ///
+    /// # Panics
+    /// Panics if `T` has a different number of fields than the pattern has placeholders, or if the type of a
+    /// placeholder does not match the type of its field.
+    pub fn parse<'a, T: FromPattern<'a>>(&self, line: Line<'a>) -> Result<T, ParseError> {
+        self.parser().parse(line)
+    }
+
+    /// A parser of lines into `T`, which checks the placeholders against the fields of `T` only once.
+    ///
+    /// # Panics
+    /// Panics if the pattern does not fit `T`, see [`Pattern::parse`].
+    pub fn parser<'a, T: FromPattern<'a>>(&self) -> Parser<'_, 'a, T> {
+        self.check(T::FIELDS);
+        Parser {
+            pattern: self,
+            fields: Vec::with_capacity(T::FIELDS.len()),
+            target: PhantomData,
+        }
+    }
+
+    fn check(&self, fields: &[(Extent, &str)]) {
+        let placeholders = self.type_names().count();
+
+        assert_eq!(
+            placeholders,
+            fields.len(),
+            "pattern `{}` has {placeholders} placeholders, but the target has {} fields",
+            self.source,
+            fields.len()
+        );
+
+        for (expected, (_, actual)) in self.type_names().zip(fields) {
+            if let Some(expected) = expected {
+                assert_eq!(
+                    expected, *actual,
+                    "pattern `{}` expects `{expected}`, but the field is a `{actual}`",
+                    self.source
+                );
+            }
+        }
+    }
+}
+
+/// Parses lines of a [`Pattern`] into `T`, reusing its buffer of fields for every line.
+pub struct Parser<'p, 'a, T> {
+    pattern: &'p Pattern,
+    fields: Vec<Line<'a>>,
+    target: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: FromPattern<'a>> Parser<'_, 'a, T> {
+    pub fn parse(&mut self, line: Line<'a>) -> Result<T, ParseError> {
+        self.pattern
+            .split_with(line, |i| T::FIELDS[i].0, &mut self.fields)?;
+        T::from_fields(&self.fields)
+    }
+}
+
+/// Parse a single line `s` with `pattern`. This compiles the pattern on every call, so compile it once with
+/// [`Pattern::new`] to parse many lines.
+///
+/// # Panics
+/// Panics if `pattern` is malformed or does not fit `T`, see [`Pattern::parse`].
+pub fn parse<'a, T: FromPattern<'a>>(pattern: &str, s: &'a str) -> Result<T, ParseError> {
+    let pattern = Pattern::new(pattern).unwrap_or_else(|e| panic!("{e}"));
+    pattern.parse(Line::new(s, 1, 1))
+}
+
+/// Parse every line of `input` with `pattern`.
+///
+/// # Panics
+/// Panics if `pattern` is malformed or does not fit `T`, see [`Pattern::parse`].
+pub fn parse_lines<'a, T: FromPattern<'a>>(
+    pattern: &str,
+    input: &'a str,
+) -> Result<Vec<T>, ParseError> {
+    let pattern = Pattern::new(pattern).unwrap_or_else(|e| panic!("{e}"));
+    let mut parser = pattern.parser();
+    lines(input).map(|line| parser.parse(line)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Extent, FromPattern, HasPattern, Pattern, parse, parse_lines};
+    use crate::parse::{ErrorKind, Line};
+
+    #[derive(Debug, PartialEq, FromPattern)]
+    #[pattern("{dir:char}{steps:i64}")]
+    struct Step {
+        dir: char,
+        steps: i64,
+    }
+
+    #[derive(Debug, PartialEq, FromPattern)]
+    #[pattern("{name:str} -> {targets}")]
+    struct Module<'a> {
+        targets: Line<'a>,
+        name: &'a str,
+    }
+
+    #[test]
+    fn parses_tuples() {
+        let ranges: Vec<(u64, u64)> = parse_lines("{}-{}", "3-5\n10-14\n").unwrap();
+        assert_eq!(ranges, [(3, 5), (10, 14)]);
+
+        let parsed: Vec<(String, i32, char)> =
+            parse_lines("{name} is {age:i32} {{{grade}}}", "Bob Ross is -3 {A}").unwrap();
+        assert_eq!(parsed, [("Bob Ross".to_string(), -3, 'A')]);
+        assert_eq!(parse("x={}", "x=7"), Ok((7_u8,)));
+    }
+
+    #[test]
+    fn parses_derived_structs() {
+        let steps = Step::parse_lines("L68\nR48\n").unwrap();
+        assert_eq!(
+            steps,
+            [
+                Step {
+                    dir: 'L',
+                    steps: 68
+                },
+                Step {
+                    dir: 'R',
+                    steps: 48
+                }
+            ]
+        );
+        assert_eq!(
+            "L-5".parse(),
+            Ok(Step {
+                dir: 'L',
+                steps: -5
+            })
+        );
+
+        let module = Module::parse_line(Line::new("broadcaster -> a, b", 1, 1)).unwrap();
+        assert_eq!(module.name, "broadcaster");
+        assert_eq!(module.targets.column(), 16);
+    }
+
+    #[test]
+    fn reuses_parsers() {
+        let pattern = Pattern::new("{x:i32},{y:i32}").unwrap();
+        let mut parser = pattern.parser::<(i32, i32)>();
+        assert_eq!(parser.parse(Line::new("1,-2", 1, 1)), Ok((1, -2)));
+        assert_eq!(parser.parse(Line::new("30,4", 2, 1)), Ok((30, 4)));
+
+        let mut fields = vec![];
+        pattern
+            .split(
+                Line::new("5,6", 1, 1),
+                &[Extent::Integer { signed: true }; 2],
+                &mut fields,
+            )
+            .unwrap();
+        assert_eq!(
+            fields.iter().map(|f| f.as_str()).collect::<Vec<_>>(),
+            ["5", "6"]
+        );
+    }
+
+    #[test]
+    fn reports_positions() {
+        let error = parse_lines::<(u64, u64)>("{}-{}", "3-5\n10_14\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 3: expected `-`");
+
+        let error = "Lx".parse::<Step>().unwrap_err();
+        assert_eq!(
+            (error.column, error.kind),
+            (2, ErrorKind::ExpectedDigit(Some('x')))
+        );
+
+        let error = "L5 ".parse::<Step>().unwrap_err();
+        assert_eq!(error.kind, ErrorKind::TrailingCharacters(' '));
+
+        let error = parse_lines::<(&str, u8)>("{} = {}", "a - 1").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::MissingSeparator(" = ".into()));
+    }
+
+    #[test]
+    fn rejects_malformed_patterns() {
+        assert!(Pattern::new("{a").is_err());
+        assert!(Pattern::new("a}").is_err());
+        assert!(Pattern::new("{{a}}").is_ok());
+        assert_eq!(
+            Pattern::new("{a:u8}-{}")
+                .unwrap()
+                .names()
+                .collect::<Vec<_>>(),
+            [Some("a"), None]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "expects `u8`, but the field is a `i64`")]
+    fn panics_for_mismatched_types() {
+        let _ = parse_lines::<(i64,)>("{n:u8}", "1");
+    }
+}
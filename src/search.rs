@@ -0,0 +1,451 @@
+//! Graph searches over nodes whose neighbours are given by a closure.
+//!
+//! Nodes can be anything hashable, e.g. grid positions or whole puzzle states. The searches that explore
+//! everything reachable return [`Reached`], which holds the distances and predecessors of all nodes and
+//! reconstructs paths. The searches for a goal stop early and return a [`Path`].
+//!
+//! ```
+//! use advent_of_code::search;
+//!
+//! // steps of +1 or *2 from 1 to 10.
+//! let path = search::bfs_to(1, |&n| [n + 1, n * 2].into_iter().filter(|n| *n <= 10), |&n| n == 10);
+//! assert_eq!(path.unwrap().nodes, [1, 2, 4, 5, 10]);
+//! ```
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use grid::Grid;
+
+use crate::geometry::{Neighbourhood, Position};
+
+/// A path through the graph and its total cost, which is the number of steps for unweighted searches.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, C> {
+    /// Nodes from the start to the goal, both included.
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+/// All nodes reached by a search, with their distance from the start and their predecessor on a shortest
+/// path.
+#[derive(Clone, Debug)]
+pub struct Reached<N, C> {
+    pub distances: HashMap<N, C>,
+    pub predecessors: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Reached<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Reached {
+            distances: HashMap::from([(start, zero)]),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.distances.contains_key(node)
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// A shortest path from the start to `goal`, if it was reached.
+    pub fn path_to(&self, goal: &N) -> Option<Path<N, C>> {
+        let cost = self.distance(goal)?;
+        let nodes = path(&self.predecessors, goal.clone());
+        Some(Path { nodes, cost })
+    }
+}
+
+/// Walk back the predecessors from `goal` to the start.
+fn path<N: Clone + Eq + Hash>(predecessors: &HashMap<N, N>, goal: N) -> Vec<N> {
+    let mut nodes = vec![goal];
+    while let Some(previous) = predecessors.get(nodes.last().unwrap()) {
+        nodes.push(previous.clone());
+    }
+    nodes.reverse();
+    nodes
+}
+
+/// Breadth-first search from `start`, stopping at the first node for which `is_goal` holds.
+fn breadth_first<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (Reached<N, usize>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = Reached::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return (reached, Some(node));
+        }
+
+        let distance = reached.distances[&node] + 1;
+        for next in neighbours(&node) {
+            if !reached.contains(&next) {
+                reached.distances.insert(next.clone(), distance);
+                reached.predecessors.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    (reached, None)
+}
+
+/// Breadth-first search of everything reachable from `start`, with the fewest steps to each node.
+pub fn bfs<N, I>(start: N, neighbours: impl FnMut(&N) -> I) -> Reached<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    breadth_first(start, neighbours, |_| false).0
+}
+
+/// A path with the fewest steps from `start` to a node for which `is_goal` holds.
+pub fn bfs_to<N, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (reached, goal) = breadth_first(start, neighbours, is_goal);
+    reached.path_to(&goal?)
+}
+
+/// Nodes reachable from `start` in depth-first pre-order, i.e. every node is listed before the nodes
+/// discovered from it. Neighbours are visited in the order they are returned.
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::new();
+    let mut order = vec![];
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+
+        let mut next: Vec<N> = neighbours(&node)
+            .into_iter()
+            .filter(|n| !visited.contains(n))
+            .collect();
+        next.reverse();
+        stack.extend(next);
+        order.push(node);
+    }
+
+    order
+}
+
+/// All nodes reachable from `start`, including it.
+pub fn flood_fill<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::new();
+    fill(start, &mut neighbours, &mut visited)
+}
+
+/// The nodes reachable from `start` that are not in `visited` yet. They are added to `visited`, too.
+fn fill<N, I>(
+    start: N,
+    neighbours: &mut impl FnMut(&N) -> I,
+    visited: &mut HashSet<N>,
+) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut filled = HashSet::new();
+    if !visited.insert(start.clone()) {
+        return filled;
+    }
+
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for next in neighbours(&node) {
+            if visited.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+        filled.insert(node);
+    }
+
+    filled
+}
+
+/// Partition `nodes` into groups that are reachable from each other. Components are ordered by their
+/// first node in `nodes`, and nodes outside of `nodes` that are reached are part of the components, too.
+/// Neighbours should be symmetric, otherwise a node may end up in the component of an earlier node only.
+pub fn connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Vec<HashSet<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+
+    nodes
+        .into_iter()
+        .filter_map(|node| {
+            let component = fill(node, &mut neighbours, &mut seen);
+            (!component.is_empty()).then_some(component)
+        })
+        .collect()
+}
+
+/// Dijkstra's algorithm from `start`, stopping at the first settled node for which `is_goal` holds.
+/// `heuristic` turns it into A*.
+fn best_first<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (Reached<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut reached = Reached::new(start.clone(), C::default());
+    let mut settled = HashSet::new();
+    // nodes are referred to by their index in `nodes`, so that they don't have to be `Ord`.
+    let mut nodes = vec![start.clone()];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+
+    while let Some(Reverse((_, index))) = heap.pop() {
+        let node = nodes[index].clone();
+        if !settled.insert(node.clone()) {
+            continue;
+        }
+        if is_goal(&node) {
+            return (reached, Some(node));
+        }
+
+        let distance = reached.distances[&node];
+        for (next, cost) in neighbours(&node) {
+            let next_distance = distance + cost;
+            if reached.distance(&next).is_some_and(|d| d <= next_distance) {
+                continue;
+            }
+
+            reached.distances.insert(next.clone(), next_distance);
+            reached.predecessors.insert(next.clone(), node.clone());
+            heap.push(Reverse((next_distance + heuristic(&next), nodes.len())));
+            nodes.push(next);
+        }
+    }
+
+    (reached, None)
+}
+
+/// Dijkstra's algorithm over everything reachable from `start`, with the lowest cost to each node.
+/// Neighbours are returned with the non-negative cost of the step to them.
+pub fn dijkstra<N, C, I>(start: N, neighbours: impl FnMut(&N) -> I) -> Reached<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(start, neighbours, |_| C::default(), |_| false).0
+}
+
+/// A path with the lowest cost from `start` to a node for which `is_goal` holds.
+pub fn dijkstra_to<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (reached, goal) = best_first(start, neighbours, |_| C::default(), is_goal);
+    reached.path_to(&goal?)
+}
+
+/// A* search for a path with the lowest cost from `start` to a node for which `is_goal` holds.
+/// `heuristic` estimates the remaining cost to a goal. It must never overestimate it, and the result is
+/// only guaranteed to be optimal if it is consistent, e.g. the Manhattan distance on a grid.
+pub fn astar<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (reached, goal) = best_first(start, neighbours, heuristic, is_goal);
+    reached.path_to(&goal?)
+}
+
+/// Neighbours of grid positions for the searches, as far as `can_move(from, to)` allows a step between the
+/// values of two cells.
+pub fn grid_neighbours<T>(
+    grid: &Grid<T>,
+    neighbourhood: Neighbourhood,
+    can_move: impl Fn(&T, &T) -> bool,
+) -> impl Fn(&Position) -> Vec<Position> {
+    move |&position| {
+        neighbourhood
+            .positions(position, grid.rows(), grid.cols())
+            .filter(|&next| can_move(&grid[position], &grid[next]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Path, astar, bfs, bfs_to, connected_components, dfs, dijkstra, dijkstra_to, flood_fill,
+        grid_neighbours,
+    };
+    use crate::geometry::{Neighbourhood, Position};
+    use grid::Grid;
+    use std::collections::HashSet;
+
+    fn maze() -> Grid<char> {
+        let rows = ["S..#....", ".#.#.##.", ".#...#..", ".####.#.", "......#E"];
+        Grid::from_vec(rows.iter().flat_map(|r| r.chars()).collect(), rows[0].len())
+    }
+
+    fn open(_: &char, to: &char) -> bool {
+        *to != '#'
+    }
+
+    #[test]
+    fn finds_shortest_paths_in_grids() {
+        let grid = maze();
+        let neighbours = grid_neighbours(&grid, Neighbourhood::VonNeumann, open);
+
+        let path = bfs_to((0, 0), &neighbours, |&p| grid[p] == 'E').unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.nodes.len(), 16);
+        assert_eq!(path.nodes.first(), Some(&(0, 0)));
+        assert_eq!(path.nodes.last(), Some(&(4, 7)));
+        assert!(
+            path.nodes
+                .windows(2)
+                .all(|w| neighbours(&w[0]).contains(&w[1]))
+        );
+
+        let reached = bfs((0, 0), &neighbours);
+        assert_eq!(reached.distance(&(4, 7)), Some(15));
+        assert_eq!(reached.distance(&(4, 5)), Some(9));
+        assert_eq!(reached.path_to(&(4, 7)).unwrap(), path);
+        assert_eq!(reached.distance(&(0, 3)), None);
+        assert_eq!(reached.path_to(&(0, 0)).unwrap().nodes, [(0, 0)]);
+    }
+
+    #[test]
+    fn weighs_steps() {
+        // 1 -> 2 -> 4 costs 2, 1 -> 4 directly costs 5.
+        let edges = |&n: &u32| match n {
+            1 => vec![(2, 1), (4, 5), (3, 1)],
+            2 => vec![(4, 1)],
+            3 => vec![(4, 3)],
+            _ => vec![],
+        };
+
+        let reached = dijkstra(1, edges);
+        assert_eq!(reached.distance(&4), Some(2));
+        assert_eq!(reached.path_to(&4).unwrap().nodes, [1, 2, 4]);
+
+        assert_eq!(
+            dijkstra_to(1, edges, |&n| n == 4),
+            Some(Path {
+                nodes: vec![1, 2, 4],
+                cost: 2
+            })
+        );
+        assert_eq!(dijkstra_to(2, edges, |&n| n == 1), None);
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let grid = maze();
+        let open = grid_neighbours(&grid, Neighbourhood::VonNeumann, open);
+        // moving down costs 3, everything else 1.
+        let neighbours = |p: &Position| {
+            let from = *p;
+            open(p)
+                .into_iter()
+                .map(move |to| (to, if to.0 > from.0 { 3 } else { 1 }))
+        };
+        let goal: Position = (4, 7);
+        let manhattan = |p: &Position| goal.0.abs_diff(p.0) + goal.1.abs_diff(p.1);
+
+        let expected = dijkstra_to((0, 0), neighbours, |p| *p == goal).unwrap();
+        let path = astar((0, 0), neighbours, manhattan, |p| *p == goal).unwrap();
+        assert_eq!(path.cost, expected.cost);
+        assert_eq!(path.nodes.first(), Some(&(0, 0)));
+        assert_eq!(path.nodes.last(), Some(&goal));
+    }
+
+    #[test]
+    fn explores_depth_first() {
+        let children = |&n: &u32| {
+            if n < 4 {
+                vec![2 * n, 2 * n + 1]
+            } else {
+                vec![]
+            }
+        };
+        assert_eq!(dfs(1, children), [1, 2, 4, 5, 3, 6, 7]);
+    }
+
+    #[test]
+    fn finds_components() {
+        let grid = maze();
+        let neighbours = grid_neighbours(&grid, Neighbourhood::VonNeumann, |a, b| a == b);
+
+        let walls = flood_fill((1, 1), &neighbours);
+        assert_eq!(
+            walls,
+            HashSet::from([(1, 1), (2, 1), (3, 1), (3, 2), (3, 3), (3, 4)])
+        );
+
+        let components = connected_components([(0, 3), (1, 3), (0, 1), (1, 5)], &neighbours);
+        assert_eq!(components.len(), 3);
+        assert_eq!(components[0], HashSet::from([(0, 3), (1, 3)]));
+        assert!(components[1].contains(&(2, 4)));
+        assert!(!components[1].contains(&(4, 5)));
+        assert_eq!(components[2], HashSet::from([(1, 5), (1, 6), (2, 5)]));
+    }
+
+    #[test]
+    fn finds_many_small_components() {
+        // a checkerboard has no orthogonally connected cells of the same colour.
+        let grid = Grid::from_vec(
+            (0..140 * 140).map(|i| (i / 140 + i % 140) % 2).collect(),
+            140,
+        );
+        let neighbours = grid_neighbours(&grid, Neighbourhood::VonNeumann, |a, b| a == b);
+
+        let components = connected_components(grid.indexed_iter().map(|(p, _)| p), neighbours);
+        assert_eq!(components.len(), 140 * 140);
+        assert!(components.iter().all(|c| c.len() == 1));
+    }
+}
@@ -0,0 +1,373 @@
+//! A grid without fixed bounds, storing only the occupied cells.
+//!
+//! [`SparseGrid`] is keyed by [`Point2`], so it can grow in every direction, e.g. for spreading patterns or
+//! a board that is extended as the puzzle runs. It keeps track of the bounding box of its cells, renders
+//! them with `Display` and converts to and from the dense [`Grid`].
+//!
+//! ```
+//! use advent_of_code::geometry::Point2;
+//! use advent_of_code::sparse_grid::SparseGrid;
+//!
+//! let mut grid = SparseGrid::new();
+//! grid.insert(Point2::new(-1, 0), '#');
+//! grid.insert(Point2::new(1, 1), '@');
+//! assert_eq!(grid.bounds().unwrap().width(), 3);
+//! assert_eq!(grid.to_string(), "#..\n..@");
+//! ```
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::collections::hash_map;
+use std::fmt::Display;
+
+use grid::Grid;
+
+use crate::geometry::{Neighbourhood, Point2};
+
+/// The smallest rectangle containing a set of points, with both corners included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point2,
+    pub max: Point2,
+}
+
+impl Bounds {
+    /// The bounds of a single point.
+    pub fn new(point: Point2) -> Self {
+        Bounds {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// Grow the bounds to contain `point`.
+    #[must_use]
+    pub fn including(self, point: Point2) -> Self {
+        Bounds {
+            min: Point2::new(self.min.x.min(point.x), self.min.y.min(point.y)),
+            max: Point2::new(self.max.x.max(point.x), self.max.y.max(point.y)),
+        }
+    }
+
+    pub fn contains(&self, point: Point2) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> usize {
+        usize::try_from(self.max.x - self.min.x + 1).unwrap()
+    }
+
+    pub fn height(&self) -> usize {
+        usize::try_from(self.max.y - self.min.y + 1).unwrap()
+    }
+
+    /// All points within the bounds, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point2> + use<> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point2::new(x, y)))
+    }
+}
+
+/// Cells at arbitrary signed coordinates. Cells that were never set are empty.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2, T>,
+    /// The bounds of the cells, computed on demand after removing a cell on the edge.
+    bounds: OnceCell<Option<Bounds>>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: OnceCell::from(None),
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for SparseGrid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl<T: Eq> Eq for SparseGrid<T> {}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The bounding box of all cells, `None` if the grid is empty. After removing cells on the edge, this
+    /// takes time proportional to the number of cells once.
+    pub fn bounds(&self) -> Option<Bounds> {
+        *self.bounds.get_or_init(|| {
+            self.cells.keys().fold(None, |bounds, &p| {
+                Some(bounds.map_or_else(|| Bounds::new(p), |b: Bounds| b.including(p)))
+            })
+        })
+    }
+
+    pub fn get(&self, point: Point2) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point2) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn contains(&self, point: Point2) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    /// Set the cell at `point`, returning its previous value.
+    pub fn insert(&mut self, point: Point2, value: T) -> Option<T> {
+        if let Some(bounds) = self.bounds.get_mut() {
+            *bounds = Some(bounds.map_or_else(|| Bounds::new(point), |b| b.including(point)));
+        }
+        self.cells.insert(point, value)
+    }
+
+    /// Empty the cell at `point`, returning its value. Removing a cell on the edge may shrink the bounds, so
+    /// they are recomputed the next time they are needed.
+    pub fn remove(&mut self, point: Point2) -> Option<T> {
+        let value = self.cells.remove(&point)?;
+
+        if let Some(Some(bounds)) = self.bounds.get()
+            && (point.x == bounds.min.x
+                || point.x == bounds.max.x
+                || point.y == bounds.min.y
+                || point.y == bounds.max.y)
+        {
+            self.bounds.take();
+        }
+
+        Some(value)
+    }
+
+    /// The cell at `point`, inserting the value of `default` if it is empty.
+    pub fn get_or_insert_with(&mut self, point: Point2, default: impl FnOnce() -> T) -> &mut T {
+        if !self.contains(point) {
+            self.insert(point, default());
+        }
+        self.cells.get_mut(&point).unwrap()
+    }
+
+    /// All cells, in no particular order.
+    pub fn iter(&self) -> hash_map::Iter<'_, Point2, T> {
+        self.cells.iter()
+    }
+
+    /// The non-empty neighbours of `point`.
+    pub fn neighbours(
+        &self,
+        point: Point2,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = (Point2, &T)> {
+        neighbourhood.directions().filter_map(move |d| {
+            let neighbour = point + d;
+            self.get(neighbour).map(|value| (neighbour, value))
+        })
+    }
+
+    /// A dense grid covering the bounds, with empty cells set to `empty`, and the point of its top left
+    /// cell. Cell `(row, col)` of the dense grid is the point `origin + (col, row)`.
+    pub fn to_dense(&self, empty: T) -> (Grid<T>, Point2)
+    where
+        T: Clone,
+    {
+        let Some(bounds) = self.bounds() else {
+            return (Grid::from_vec(vec![], 0), Point2::ORIGIN);
+        };
+
+        let cells = bounds
+            .points()
+            .map(|point| self.get(point).unwrap_or(&empty).clone())
+            .collect();
+        (Grid::from_vec(cells, bounds.width()), bounds.min)
+    }
+
+    /// The cells of a dense grid for which `keep` holds, with the top left cell at the origin.
+    pub fn from_dense(grid: &Grid<T>, keep: impl Fn(&T) -> bool) -> Self
+    where
+        T: Clone,
+    {
+        grid.indexed_iter()
+            .filter(|(_, value)| keep(value))
+            .map(|(position, value)| (Point2::from_position(position), value.clone()))
+            .collect()
+    }
+}
+
+impl<T> FromIterator<(Point2, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point2, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point2, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point2, T)>>(&mut self, iter: I) {
+        for (point, value) in iter {
+            self.insert(point, value);
+        }
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    /// All cells of the dense grid, with the top left cell at the origin.
+    fn from(grid: Grid<T>) -> Self {
+        grid.indexed_into_iter()
+            .map(|(position, value)| (Point2::from_position(position), value))
+            .collect()
+    }
+}
+
+impl<T: Display> Display for SparseGrid<T> {
+    /// Render the bounds row by row, with `.` for empty cells.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(bounds) = self.bounds() else {
+            return Ok(());
+        };
+
+        for y in bounds.min.y..=bounds.max.y {
+            if y > bounds.min.y {
+                writeln!(f)?;
+            }
+            for x in bounds.min.x..=bounds.max.x {
+                match self.get(Point2::new(x, y)) {
+                    Some(value) => write!(f, "{value}")?,
+                    None => write!(f, ".")?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bounds, SparseGrid};
+    use crate::geometry::{Neighbourhood, Point2};
+    use grid::grid;
+
+    #[test]
+    fn tracks_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Point2::new(2, 3), 'a');
+        grid.insert(Point2::new(-4, 1), 'b');
+        grid.insert(Point2::new(0, 0), 'c');
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds {
+                min: Point2::new(-4, 0),
+                max: Point2::new(2, 3)
+            })
+        );
+        assert_eq!(grid.bounds().unwrap().height(), 4);
+
+        // removing an inner cell keeps the bounds, removing an edge cell shrinks them.
+        grid.insert(Point2::new(-1, 1), 'd');
+        grid.remove(Point2::new(-1, 1));
+        assert_eq!(grid.bounds().unwrap().min, Point2::new(-4, 0));
+        grid.remove(Point2::new(-4, 1));
+        assert_eq!(grid.bounds().unwrap().min, Point2::new(0, 0));
+        grid.remove(Point2::new(2, 3));
+        grid.remove(Point2::new(0, 0));
+        assert_eq!(grid.bounds(), None);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn recomputes_bounds_after_erosion() {
+        let mut grid: SparseGrid<()> = Bounds {
+            min: Point2::ORIGIN,
+            max: Point2::new(99, 99),
+        }
+        .points()
+        .map(|p| (p, ()))
+        .collect();
+        let full = grid.clone();
+
+        // peel off the outer ring until a 2x2 block is left.
+        for ring in 0..49 {
+            let outer = Bounds {
+                min: Point2::new(ring, ring),
+                max: Point2::new(99 - ring, 99 - ring),
+            };
+            for point in outer.points() {
+                if !(ring + 1..99 - ring).contains(&point.x)
+                    || !(ring + 1..99 - ring).contains(&point.y)
+                {
+                    grid.remove(point);
+                }
+            }
+        }
+
+        assert_eq!(grid.len(), 4);
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds {
+                min: Point2::new(49, 49),
+                max: Point2::new(50, 50)
+            })
+        );
+        assert_ne!(grid, full);
+    }
+
+    #[test]
+    fn renders_occupied_region() {
+        let grid: SparseGrid<char> = [(Point2::new(5, -2), '#'), (Point2::new(7, -1), '#')]
+            .into_iter()
+            .collect();
+        assert_eq!(grid.to_string(), "#..\n..#");
+        assert_eq!(SparseGrid::<char>::new().to_string(), "");
+    }
+
+    #[test]
+    fn converts_to_and_from_dense_grids() {
+        let dense = grid![['#', '.', '.']['.', '.', '#']];
+
+        let sparse = SparseGrid::from_dense(&dense, |c| *c == '#');
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.get(Point2::new(2, 1)), Some(&'#'));
+        assert_eq!(sparse.to_dense('.'), (dense.clone(), Point2::ORIGIN));
+
+        let mut sparse = SparseGrid::from(dense);
+        assert_eq!(sparse.len(), 6);
+        sparse.insert(Point2::new(-1, 0), '@');
+        let (dense, origin) = sparse.to_dense(' ');
+        assert_eq!(origin, Point2::new(-1, 0));
+        assert_eq!(dense, grid![['@', '#', '.', '.'][' ', '.', '.', '#']]);
+    }
+
+    #[test]
+    fn finds_occupied_neighbours() {
+        let mut grid = SparseGrid::new();
+        for point in [Point2::new(0, 0), Point2::new(1, 1), Point2::new(0, 1)] {
+            *grid.get_or_insert_with(point, || 0) += 1;
+        }
+
+        let neighbours: Vec<_> = grid
+            .neighbours(Point2::new(0, 0), Neighbourhood::Moore)
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(neighbours, [Point2::new(1, 1), Point2::new(0, 1)]);
+        assert_eq!(
+            grid.neighbours(Point2::new(0, 0), Neighbourhood::VonNeumann)
+                .count(),
+            1
+        );
+    }
+}
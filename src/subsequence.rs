@@ -0,0 +1,105 @@
+//! Greedy selection of subsequences with a monotonic stack.
+//!
+//! ```
+//! use advent_of_code::subsequence;
+//!
+//! let digits = [8, 1, 8, 1, 8, 1, 9, 1, 1, 1, 1, 2, 1, 1, 1];
+//! assert_eq!(subsequence::largest(&digits, 2), Some(vec![9, 2]));
+//! assert_eq!(subsequence::smallest(&digits, 3), Some(vec![1, 1, 1]));
+//! ```
+
+/// Indices of the `k` items that remain when every stacked item is dropped for which `replace(top, next)`
+/// holds, as long as enough items are left.
+fn select<T>(items: &[T], k: usize, replace: impl Fn(&T, &T) -> bool) -> Option<Vec<usize>> {
+    if k > items.len() {
+        return None;
+    }
+
+    let mut drops = items.len() - k;
+    let mut stack: Vec<usize> = Vec::with_capacity(items.len());
+
+    for (i, item) in items.iter().enumerate() {
+        // every item is pushed and popped at most once, so this runs in O(n).
+        while drops > 0
+            && let Some(&top) = stack.last()
+            && replace(&items[top], item)
+        {
+            stack.pop();
+            drops -= 1;
+        }
+        stack.push(i);
+    }
+
+    stack.truncate(k);
+    Some(stack)
+}
+
+/// Indices of the lexicographically largest subsequence of length `k`, or `None` if `items` is shorter.
+/// Of equal subsequences, the one using the earliest indices is returned.
+pub fn largest_indices<T: Ord>(items: &[T], k: usize) -> Option<Vec<usize>> {
+    select(items, k, |top, next| top < next)
+}
+
+/// Indices of the lexicographically smallest subsequence of length `k`, or `None` if `items` is shorter.
+/// Of equal subsequences, the one using the earliest indices is returned.
+pub fn smallest_indices<T: Ord>(items: &[T], k: usize) -> Option<Vec<usize>> {
+    select(items, k, |top, next| top > next)
+}
+
+/// The lexicographically largest subsequence of length `k`, e.g. the largest number that can be formed
+/// from `k` of the digits in order. `None` if `items` is shorter than `k`.
+pub fn largest<T: Ord + Clone>(items: &[T], k: usize) -> Option<Vec<T>> {
+    largest_indices(items, k).map(|indices| indices.into_iter().map(|i| items[i].clone()).collect())
+}
+
+/// The lexicographically smallest subsequence of length `k`. `None` if `items` is shorter than `k`.
+pub fn smallest<T: Ord + Clone>(items: &[T], k: usize) -> Option<Vec<T>> {
+    smallest_indices(items, k)
+        .map(|indices| indices.into_iter().map(|i| items[i].clone()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{largest, largest_indices, smallest, smallest_indices};
+
+    /// All subsequences of length `k`, in the order of their indices.
+    fn subsequences(items: &[u8], k: usize) -> Vec<Vec<u8>> {
+        (0_u32..1 << items.len())
+            .filter(|mask| mask.count_ones() as usize == k)
+            .map(|mask| {
+                (0..items.len())
+                    .filter(|i| mask & (1 << i) != 0)
+                    .map(|i| items[i])
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        // every sequence of length 6 over the digits 0..3.
+        for code in 0..3_u32.pow(6) {
+            #[allow(clippy::cast_possible_truncation)]
+            let items: Vec<u8> = (0..6).map(|i| (code / 3_u32.pow(i) % 3) as u8).collect();
+
+            for k in 0..=items.len() {
+                let all = subsequences(&items, k);
+                assert_eq!(largest(&items, k).as_ref(), all.iter().max(), "{items:?}");
+                assert_eq!(smallest(&items, k).as_ref(), all.iter().min(), "{items:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn prefers_earliest_indices() {
+        assert_eq!(largest_indices(&[3, 1, 3, 3], 2), Some(vec![0, 2]));
+        assert_eq!(smallest_indices(&[1, 2, 1, 1], 2), Some(vec![0, 2]));
+        assert_eq!(largest(&["b", "a", "c"], 2), Some(vec!["b", "c"]));
+    }
+
+    #[test]
+    fn rejects_short_input() {
+        assert_eq!(largest(&[1, 2], 3), None);
+        assert_eq!(smallest::<u8>(&[], 0), Some(vec![]));
+    }
+}
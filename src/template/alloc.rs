@@ -0,0 +1,117 @@
+//! A global allocator that counts allocations with minimal overhead.
+//! Installed as the global allocator of the library when the `count-allocations` feature is enabled.
+//! Unlike dhat, it does not record backtraces, so it can be used with optimized builds and regular timings.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+static BASELINE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static TOTAL_BYTES: AtomicU64 = AtomicU64::new(0);
+static TOTAL_BLOCKS: AtomicU64 = AtomicU64::new(0);
+
+/// Heap usage since the last call to [`reset`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocationStats {
+    /// Highest heap size reached, relative to the heap size at the time of the reset.
+    pub peak_bytes: u64,
+    /// Number of bytes allocated.
+    pub total_bytes: u64,
+    /// Number of allocations.
+    pub total_blocks: u64,
+}
+
+/// Start counting from zero, e.g. before running a solution part.
+pub fn reset() {
+    let current = CURRENT_BYTES.load(Ordering::Relaxed);
+    BASELINE_BYTES.store(current, Ordering::Relaxed);
+    PEAK_BYTES.store(current, Ordering::Relaxed);
+    TOTAL_BYTES.store(0, Ordering::Relaxed);
+    TOTAL_BLOCKS.store(0, Ordering::Relaxed);
+}
+
+/// Read the heap usage since the last call to [`reset`].
+pub fn stats() -> AllocationStats {
+    let peak = PEAK_BYTES.load(Ordering::Relaxed);
+    let baseline = BASELINE_BYTES.load(Ordering::Relaxed);
+
+    AllocationStats {
+        peak_bytes: peak.saturating_sub(baseline) as u64,
+        total_bytes: TOTAL_BYTES.load(Ordering::Relaxed),
+        total_blocks: TOTAL_BLOCKS.load(Ordering::Relaxed),
+    }
+}
+
+fn record_alloc(size: usize) {
+    let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+    TOTAL_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    TOTAL_BLOCKS.fetch_add(1, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    CURRENT_BYTES.fetch_sub(size, Ordering::Relaxed);
+}
+
+/// Wraps the system allocator and counts every allocation.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            // like dhat, count a reallocation as freeing the old block and allocating a new one.
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CountingAlloc, reset, stats};
+    use std::alloc::{GlobalAlloc, Layout};
+
+    #[test]
+    fn counts_allocations() {
+        let alloc = CountingAlloc;
+        let layout = Layout::from_size_align(64, 8).unwrap();
+
+        reset();
+
+        unsafe {
+            let a = alloc.alloc(layout);
+            let b = alloc.alloc(layout);
+            alloc.dealloc(a, layout);
+            let b = alloc.realloc(b, layout, 128);
+            alloc.dealloc(b, Layout::from_size_align(128, 8).unwrap());
+        }
+
+        let stats = stats();
+        assert_eq!(stats.total_blocks, 3);
+        assert_eq!(stats.total_bytes, 256);
+        assert_eq!(stats.peak_bytes, 128);
+    }
+}
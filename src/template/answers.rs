@@ -0,0 +1,282 @@
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// A solved part of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Solved {
+    /// The answer that was accepted.
+    Answer(String),
+    /// The part was reported as already solved, so the accepted answer is not known.
+    Unverified,
+}
+
+impl Solved {
+    /// The accepted answer, if it is known.
+    pub fn answer(&self) -> Option<&str> {
+        match self {
+            Solved::Answer(answer) => Some(answer),
+            Solved::Unverified => None,
+        }
+    }
+}
+
+/// Represents the accepted answers for a single day.
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<Solved>,
+    pub part_2: Option<Solved>,
+}
+
+impl Answer {
+    /// Number of stars earned for this day.
+    pub fn stars(&self) -> usize {
+        usize::from(self.part_1.is_some()) + usize::from(self.part_2.is_some())
+    }
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(ANSWERS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Record `answer` as the accepted answer for `part` of `day`.
+    pub fn insert(&mut self, day: Day, part: u8, answer: &str) {
+        if let Some(entry) = self.part_mut(day, part) {
+            *entry = Some(Solved::Answer(answer.into()));
+        }
+    }
+
+    /// Record the star of `part` of `day` without a known answer, unless it is solved already.
+    pub fn insert_star(&mut self, day: Day, part: u8) {
+        if let Some(entry) = self.part_mut(day, part) {
+            entry.get_or_insert(Solved::Unverified);
+        }
+    }
+
+    fn part_mut(&mut self, day: Day, part: u8) -> Option<&mut Option<Solved>> {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let entry = &mut self.data[index];
+        match part {
+            1 => Some(&mut entry.part_1),
+            2 => Some(&mut entry.part_2),
+            _ => None,
+        }
+    }
+
+    /// Get the accepted answer for `part` of `day`, if it is known.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.solved(day, part)?.answer()
+    }
+
+    /// Whether `part` of `day` earned a star, with or without a known answer.
+    pub fn is_solved(&self, day: Day, part: u8) -> bool {
+        self.solved(day, part).is_some()
+    }
+
+    fn solved(&self, day: Day, part: u8) -> Option<&Solved> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_ref(),
+            2 => answer.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Total number of stars earned.
+    pub fn total_stars(&self) -> usize {
+        self.data.iter().map(Answer::stars).sum()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part_1".into(), solved_to_json(value.part_1.as_ref()));
+        map.insert("part_2".into(), solved_to_json(value.part_2.as_ref()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .and_then(solved_from_json)
+            .ok_or("Expected answer.part_1 to be null, true or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .and_then(solved_from_json)
+            .ok_or("Expected answer.part_2 to be null, true or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1,
+            part_2,
+        })
+    }
+}
+
+/// Accepted answers are stored as strings, stars without a known answer as `true`.
+fn solved_to_json(value: Option<&Solved>) -> JsonValue {
+    match value {
+        Some(Solved::Answer(answer)) => JsonValue::String(answer.clone()),
+        Some(Solved::Unverified) => JsonValue::Boolean(true),
+        None => JsonValue::Null,
+    }
+}
+
+fn solved_from_json(value: &JsonValue) -> Option<Option<Solved>> {
+    match value {
+        JsonValue::String(answer) => Some(Some(Solved::Answer(answer.clone()))),
+        JsonValue::Boolean(true) => Some(Some(Solved::Unverified)),
+        JsonValue::Null => Some(None),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::Answers;
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "42", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.data.len(), 1);
+        assert_eq!(answers.get(day!(1), 1), Some("42"));
+        assert_eq!(answers.get(day!(1), 2), None);
+        assert_eq!(answers.total_stars(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "26" }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn inserts_sorted_answers() {
+        let mut answers = Answers::default();
+        answers.insert(day!(3), 1, "1");
+        answers.insert(day!(1), 2, "2");
+        answers.insert(day!(3), 2, "3");
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(3), 2), Some("3"));
+        assert_eq!(answers.total_stars(), 3);
+    }
+
+    #[test]
+    fn keeps_stars_without_answers() {
+        let mut answers = Answers::default();
+        answers.insert_star(day!(2), 1);
+        answers.insert(day!(2), 2, "7");
+        answers.insert_star(day!(2), 2);
+        assert!(answers.is_solved(day!(2), 1));
+        assert_eq!(answers.get(day!(2), 1), None);
+        assert_eq!(answers.get(day!(2), 2), Some("7"));
+        assert_eq!(answers.total_stars(), 2);
+
+        let json = JsonValue::from(answers).stringify().unwrap();
+        let answers = Answers::try_from(json).unwrap();
+        assert!(answers.is_solved(day!(2), 1));
+        assert_eq!(answers.get(day!(2), 1), None);
+        assert_eq!(answers.get(day!(2), 2), Some("7"));
+    }
+}
@@ -1,10 +1,12 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
+    env,
     fmt::Display,
     process::{Command, Output, Stdio},
+    time::Duration,
 };
 
-use crate::template::Day;
+use crate::template::{Day, get_year};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -25,30 +27,114 @@ impl Display for AocCommandError {
     }
 }
 
+/// Hint given by the puzzle page for a wrong answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// Outcome of submitting an answer, parsed from the output of aoc-cli.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong(Option<Hint>),
+    /// An answer was submitted too recently, with the remaining wait time if known.
+    RateLimited(Option<Duration>),
+    /// The part was already solved, or is not unlocked yet.
+    AlreadySolved,
+    /// The output of aoc-cli did not match any known response.
+    Unknown,
+}
+
+impl Verdict {
+    /// Parse the response message of the puzzle page, as printed by aoc-cli.
+    pub fn parse(output: &str) -> Self {
+        if output.contains("That's the right answer") {
+            Verdict::Correct
+        } else if output.contains("That's not the right answer") {
+            Verdict::Wrong(if output.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if output.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            })
+        } else if output.contains("You gave an answer too recently") {
+            Verdict::RateLimited(parse_wait_time(output))
+        } else if output.contains("Did you already complete it") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Exit code of a solution binary after submitting, so that scripts can react to the verdict.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Verdict::Correct => 0,
+            Verdict::Wrong(_) => 2,
+            Verdict::RateLimited(_) => 3,
+            Verdict::AlreadySolved => 4,
+            Verdict::Unknown => 5,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "Correct answer! ⭐️"),
+            Verdict::Wrong(Some(Hint::TooHigh)) => write!(f, "Wrong answer, too high."),
+            Verdict::Wrong(Some(Hint::TooLow)) => write!(f, "Wrong answer, too low."),
+            Verdict::Wrong(None) => write!(f, "Wrong answer."),
+            Verdict::RateLimited(Some(wait)) => {
+                write!(f, "Rate limited, wait {wait:?} before submitting again.")
+            }
+            Verdict::RateLimited(None) => write!(f, "Rate limited, wait before submitting again."),
+            Verdict::AlreadySolved => write!(f, "Already solved, or the part is not unlocked yet."),
+            Verdict::Unknown => write!(f, "Could not determine the verdict of aoc-cli."),
+        }
+    }
+}
+
+/// Parse the wait time of a rate limit message, e.g. `You have 1m 5s left to wait.`
+fn parse_wait_time(output: &str) -> Option<Duration> {
+    let (_, rest) = output.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    wait.split_whitespace()
+        .map(|token| {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Returns the aoc-cli executable configured via the `AOC_CLI` environment variable, defaulting to `aoc`.
+#[must_use]
+pub fn get_executable() -> String {
+    env::var("AOC_CLI")
+        .ok()
+        .filter(|x| !x.trim().is_empty())
+        .unwrap_or_else(|| "aoc".into())
+}
+
 pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
+    Command::new(get_executable())
         .arg("-V")
         .output()
         .map_err(|_| AocCommandError::CommandNotFound)?;
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
-
-    call_aoc_cli(&args)
-}
-
 pub fn download(day: Day) -> Result<Output, AocCommandError> {
     let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
@@ -65,19 +151,35 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
         day,
     );
 
-    let output = call_aoc_cli(&args)?;
+    let output = call_aoc_cli(&args, Stdio::inherit())?;
     println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Arguments passed to aoc-cli to submit `result` for `part` of `day`.
+pub fn submit_args(day: Day, part: u8, result: &str) -> Vec<String> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+    args
+}
+
+pub fn submit(day: Day, part: u8, result: &str) -> Result<Verdict, AocCommandError> {
+    let args = submit_args(day, part, result);
+
+    // stdout is captured to parse the verdict, echo it so the full response is still shown.
+    let output = call_aoc_cli(&args, Stdio::piped()).inspect_err(|e| {
+        if let AocCommandError::BadExitStatus(output) = e {
+            print!("{}", String::from_utf8_lossy(&output.stdout));
+        }
+    })?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    print!("{stdout}");
+
+    Ok(Verdict::parse(&stdout))
 }
 
 fn get_input_path(day: Day) -> String {
@@ -88,13 +190,6 @@ fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
-}
-
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
@@ -108,11 +203,11 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+fn call_aoc_cli(args: &[String], stdout: Stdio) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
+    let output = Command::new(get_executable())
         .args(args)
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
@@ -123,3 +218,57 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Hint, Verdict};
+    use std::time::Duration;
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::parse(
+                "That's the right answer! You are one gold star closer to saving Christmas."
+            ),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse(
+                "That's not the right answer; your answer is too low. Please wait one minute before trying again."
+            ),
+            Verdict::Wrong(Some(Hint::TooLow))
+        );
+        assert_eq!(
+            Verdict::parse(
+                "That's not the right answer. If you're stuck, make sure you're using the full input data."
+            ),
+            Verdict::Wrong(None)
+        );
+        assert_eq!(
+            Verdict::parse(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(
+            Verdict::parse("Error: connection refused"),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn parses_rate_limit_wait_time() {
+        assert_eq!(
+            Verdict::parse(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."
+            ),
+            Verdict::RateLimited(Some(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            Verdict::parse(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again."
+            ),
+            Verdict::RateLimited(None)
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Configuration of `runner::bench`, shared by `cargo time` and the solution binaries it invokes.
+//! Defaults can be set via environment variables, e.g. in the `[env]` section of `.cargo/config.toml`,
+//! and are overridden by command-line flags.
+use std::{env, time::Duration};
+
+use crate::template::timings::duration_to_nanos;
+
+/// Settings that control how long and how often a solution part is benched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate time budget of each part.
+    pub target: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Time spent running a part before taking samples.
+    pub warm_up: Duration,
+    /// Pin the benchmark to this CPU (Linux only).
+    pub pin_cpu: Option<usize>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            target: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+            warm_up: Duration::ZERO,
+            pin_cpu: None,
+        }
+    }
+}
+
+/// Parse a duration like `200ms` or `2s`.
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let nanos = duration_to_nanos(s.trim())
+        .filter(|x| x.is_finite() && *x >= 0_f64)
+        .ok_or_else(|| format!("invalid duration `{s}`, expected e.g. `500ms` or `2s`"))?;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok(Duration::from_nanos(nanos.round() as u64))
+}
+
+fn parse_number<T: std::str::FromStr>(s: &str) -> Result<T, String> {
+    s.trim()
+        .parse()
+        .map_err(|_| format!("invalid number `{s}`"))
+}
+
+impl BenchConfig {
+    /// Read the configuration from `AOC_BENCH_*` environment variables, falling back to defaults.
+    pub fn from_env() -> Self {
+        let mut config = BenchConfig::default();
+
+        let read = |key: &str| env::var(key).ok().filter(|x| !x.trim().is_empty());
+        let warn = |key: &str, e: String| eprintln!("Ignoring {key}: {e}.");
+
+        if let Some(x) = read("AOC_BENCH_TARGET") {
+            match parse_duration(&x) {
+                Ok(x) => config.target = x,
+                Err(e) => warn("AOC_BENCH_TARGET", e),
+            }
+        }
+
+        if let Some(x) = read("AOC_BENCH_MIN_SAMPLES") {
+            match parse_number(&x) {
+                Ok(x) => config.min_samples = x,
+                Err(e) => warn("AOC_BENCH_MIN_SAMPLES", e),
+            }
+        }
+
+        if let Some(x) = read("AOC_BENCH_MAX_SAMPLES") {
+            match parse_number(&x) {
+                Ok(x) => config.max_samples = x,
+                Err(e) => warn("AOC_BENCH_MAX_SAMPLES", e),
+            }
+        }
+
+        if let Some(x) = read("AOC_BENCH_WARM_UP") {
+            match parse_duration(&x) {
+                Ok(x) => config.warm_up = x,
+                Err(e) => warn("AOC_BENCH_WARM_UP", e),
+            }
+        }
+
+        if let Some(x) = read("AOC_BENCH_PIN_CPU") {
+            match parse_number(&x) {
+                Ok(x) => config.pin_cpu = Some(x),
+                Err(e) => warn("AOC_BENCH_PIN_CPU", e),
+            }
+        }
+
+        config
+    }
+
+    /// Read the configuration from the environment and apply overrides passed as command-line flags.
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let mut config = BenchConfig::from_env();
+
+        if let Some(x) = args.opt_value_from_fn("--target", parse_duration)? {
+            config.target = x;
+        }
+
+        if let Some(x) = args.opt_value_from_fn("--min-samples", parse_number)? {
+            config.min_samples = x;
+        }
+
+        if let Some(x) = args.opt_value_from_fn("--max-samples", parse_number)? {
+            config.max_samples = x;
+        }
+
+        if let Some(x) = args.opt_value_from_fn("--warm-up", parse_duration)? {
+            config.warm_up = x;
+        }
+
+        if let Some(x) = args.opt_value_from_fn("--pin-cpu", parse_number)? {
+            config.pin_cpu = Some(x);
+        }
+
+        Ok(config)
+    }
+
+    /// Check for settings that can't be combined.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_samples == 0 {
+            return Err("`--min-samples` has to be at least 1.".into());
+        }
+
+        if self.min_samples > self.max_samples {
+            return Err("`--min-samples` cannot be larger than `--max-samples`.".into());
+        }
+
+        Ok(())
+    }
+
+    /// Command-line flags that pass this configuration on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--target".into(),
+            format!("{:?}", self.target),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+            "--warm-up".into(),
+            format!("{:?}", self.warm_up),
+        ];
+
+        if let Some(cpu) = self.pin_cpu {
+            args.extend(["--pin-cpu".into(), cpu.to_string()]);
+        }
+
+        args
+    }
+
+    /// Number of samples to take of a part that took `base_time` on its first run.
+    pub fn samples(&self, base_time: &Duration) -> u128 {
+        (self.target.as_nanos() / base_time.as_nanos().max(10))
+            .clamp(self.min_samples, self.max_samples)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BenchConfig;
+    use std::{ffi::OsString, time::Duration};
+
+    fn parse(args: &[&str]) -> Result<BenchConfig, pico_args::Error> {
+        let args: Vec<OsString> = args.iter().map(OsString::from).collect();
+        BenchConfig::parse(&mut pico_args::Arguments::from_vec(args))
+    }
+
+    #[test]
+    fn parses_flags() {
+        let config = parse(&[
+            "--target",
+            "200ms",
+            "--min-samples",
+            "5",
+            "--warm-up",
+            "1.5s",
+            "--pin-cpu",
+            "2",
+        ])
+        .unwrap();
+        assert_eq!(config.target, Duration::from_millis(200));
+        assert_eq!(config.min_samples, 5);
+        assert_eq!(config.warm_up, Duration::from_millis(1500));
+        assert_eq!(config.pin_cpu, Some(2));
+        assert!(parse(&["--target", "soon"]).is_err());
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        let config = BenchConfig {
+            target: Duration::from_micros(1500),
+            min_samples: 3,
+            max_samples: 50,
+            warm_up: Duration::from_millis(100),
+            pin_cpu: Some(1),
+        };
+        let args = config.to_args();
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        assert_eq!(parse(&args).unwrap(), config);
+    }
+
+    #[test]
+    fn clamps_samples() {
+        let config = BenchConfig {
+            target: Duration::from_millis(100),
+            min_samples: 5,
+            max_samples: 50,
+            ..Default::default()
+        };
+        assert_eq!(config.samples(&Duration::from_millis(10)), 10);
+        assert_eq!(config.samples(&Duration::from_secs(1)), 5);
+        assert_eq!(config.samples(&Duration::from_nanos(1)), 50);
+    }
+
+    #[test]
+    fn validates_sample_range() {
+        let config = BenchConfig {
+            min_samples: 100,
+            max_samples: 10,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(BenchConfig::default().validate().is_ok());
+    }
+}
@@ -0,0 +1,102 @@
+/// Options that control how solution binaries are compiled, shared by `solve`, `all` and `time`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BuildOptions {
+    pub release: bool,
+    /// Profile heap allocations with dhat. Implies the `dhat` profile.
+    pub dhat: bool,
+    /// Count allocations with the lightweight counting allocator.
+    pub count_allocations: bool,
+    /// Measure CPU time and hardware counters on Linux.
+    pub cpu_time: bool,
+}
+
+impl BuildOptions {
+    /// Check for flags that can't be combined.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.dhat && self.count_allocations {
+            return Err(
+                "The `--dhat` and `--count-allocations` flags cannot be used together.".into(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Name of the profile solutions are compiled with.
+    pub fn profile_name(&self) -> &'static str {
+        if self.dhat {
+            "dhat"
+        } else if self.release {
+            "release"
+        } else {
+            "dev"
+        }
+    }
+
+    /// Arguments passed to `cargo run` to select the profile and features.
+    pub fn cargo_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.dhat {
+            // the dhat profile inherits from release and adds debug info for dhat.
+            args.extend(["--profile".into(), "dhat".into()]);
+        } else if self.release {
+            args.push("--release".into());
+        }
+
+        let features: Vec<&str> = [
+            (self.dhat, "dhat-heap"),
+            (self.count_allocations, "count-allocations"),
+            (self.cpu_time, "cpu-time"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, feature)| enabled.then_some(feature))
+        .collect();
+
+        if !features.is_empty() {
+            args.extend(["--features".into(), features.join(",")]);
+        }
+
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BuildOptions;
+
+    #[test]
+    fn builds_cargo_args() {
+        let options = BuildOptions {
+            release: true,
+            cpu_time: true,
+            count_allocations: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            options.cargo_args(),
+            ["--release", "--features", "count-allocations,cpu-time"]
+        );
+        assert_eq!(options.profile_name(), "release");
+
+        let options = BuildOptions {
+            release: true,
+            dhat: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            options.cargo_args(),
+            ["--profile", "dhat", "--features", "dhat-heap"]
+        );
+        assert!(BuildOptions::default().cargo_args().is_empty());
+    }
+
+    #[test]
+    fn rejects_dhat_with_counting_allocator() {
+        let options = BuildOptions {
+            dhat: true,
+            count_allocations: true,
+            ..Default::default()
+        };
+        assert!(options.validate().is_err());
+    }
+}
@@ -1,5 +1,11 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::path::Path;
+
+use crate::template::{BuildOptions, all_days, run_multi::run_multi};
 
 pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+    let options = BuildOptions {
+        release: is_release,
+        ..Default::default()
+    };
+    run_multi(&all_days().collect(), options, None, Path::new("."));
 }
@@ -0,0 +1,36 @@
+use std::collections::HashSet;
+use std::process;
+
+use crate::template::compare::compare;
+use crate::template::{BenchConfig, BuildOptions, Day, all_days};
+
+pub fn handle(
+    day: Option<Day>,
+    rev: &str,
+    rounds: usize,
+    options: BuildOptions,
+    bench: BenchConfig,
+) {
+    if let Err(e) = options.validate().and_then(|()| bench.validate()) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    if rounds == 0 {
+        eprintln!("The `--rounds` flag has to be at least 1.");
+        process::exit(1);
+    }
+
+    let days: HashSet<Day> = day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+
+    // benchmarks are always run with optimizations.
+    let options = BuildOptions {
+        release: true,
+        ..options
+    };
+
+    if let Err(e) = compare(rev, &days, options, &bench, rounds) {
+        eprintln!("Failed to compare with `{rev}`: {e}");
+        process::exit(1);
+    }
+}
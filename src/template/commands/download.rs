@@ -4,7 +4,8 @@ use std::process;
 pub fn handle(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            "command \"{}\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.",
+            aoc_cli::get_executable()
         );
         process::exit(1);
     }
@@ -1,4 +1,5 @@
 pub mod all;
+pub mod compare;
 pub mod download;
 pub mod read;
 pub mod scaffold;
@@ -1,17 +1,54 @@
-use std::process;
+use std::{env, fs, process};
 
-use crate::template::{Day, aoc_cli};
+use crate::template::{Day, markdown};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
+const DEFAULT_WIDTH: usize = 80;
+
+pub fn handle(day: Day, part: Option<u8>) {
+    let puzzle_path = format!("data/puzzles/{day}.md");
+
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
         eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            "Could not read puzzle description \"{puzzle_path}\". Try running \"cargo download {day}\" to download it."
         );
         process::exit(1);
-    }
+    };
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let puzzle = match part {
+        Some(part) => match markdown::select_part(&puzzle, part) {
+            Some(section) => section,
+            None => {
+                eprintln!("Part {part} of day {day} is not unlocked yet.");
+                process::exit(1);
+            }
+        },
+        None => &puzzle,
     };
+
+    println!("{}", markdown::render(puzzle, terminal_width()));
+}
+
+/// Terminal width, from `$COLUMNS` if set, otherwise from the terminal on stdout, falling back to 80 columns.
+/// Shells don't export `$COLUMNS` to child processes, so it only serves as an override.
+fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|x| x.parse::<usize>().ok())
+        .filter(|&x| x > 0)
+        .or_else(tty_width)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+#[cfg(target_os = "linux")]
+fn tty_width() -> Option<usize> {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    match unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &raw mut size) } {
+        0 if size.ws_col > 0 => Some(usize::from(size.ws_col)),
+        _ => None,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn tty_width() -> Option<usize> {
+    None
 }
@@ -1,5 +1,5 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
     process,
 };
@@ -27,6 +27,48 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
+const BENCH_PATH: &str = "benches/days.rs";
+const BENCH_LIST_START: &str = "benchmark_days! {";
+
+/// Add `day` to the list of the bench harness, keeping the list sorted.
+/// Returns `None` if the list is missing or already contains the day.
+fn with_bench_entry(contents: &str, day: Day) -> Option<String> {
+    let entry = format!(
+        "    ({}, day_{day}, \"../src/bin/{day}.rs\"),",
+        day.into_inner()
+    );
+    if contents.lines().any(|line| line == entry) {
+        return None;
+    }
+
+    let lines: Vec<&str> = contents.lines().collect();
+    let start = lines.iter().position(|line| *line == BENCH_LIST_START)? + 1;
+    let end = start + lines[start..].iter().position(|line| *line == "}")?;
+
+    let mut entries: Vec<&str> = lines[start..end].to_vec();
+    entries.push(&entry);
+    // entries start with the day, so comparing the padded module name sorts them by day.
+    entries.sort_by_key(|line| line.split(", ").nth(1).map(str::to_string));
+
+    let mut updated: Vec<&str> = lines[..start].to_vec();
+    updated.extend(entries);
+    updated.extend(&lines[end..]);
+    Some(updated.join("\n") + "\n")
+}
+
+fn add_to_benches(day: Day) {
+    let Ok(contents) = fs::read_to_string(BENCH_PATH) else {
+        return;
+    };
+
+    if let Some(updated) = with_bench_entry(&contents, day) {
+        match fs::write(BENCH_PATH, updated) {
+            Ok(()) => println!("Added day {day} to \"{BENCH_PATH}\""),
+            Err(e) => eprintln!("Failed to add day {day} to \"{BENCH_PATH}\": {e}"),
+        }
+    }
+}
+
 pub fn handle(day: Day, overwrite: bool) {
     let input_path = format!("data/inputs/{day}.txt");
     let example_path = format!("data/examples/{day}.txt");
@@ -74,6 +116,8 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    add_to_benches(day);
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
@@ -1,26 +1,58 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::submissions::ExampleStatus;
+use crate::template::{BuildOptions, Day, watch};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    day: Day,
+    options: BuildOptions,
+    submit_part: Option<u8>,
+    yes: bool,
+    dry_run: bool,
+    watch: bool,
+    test: bool,
+) {
+    if let Err(e) = options.validate() {
+        eprintln!("{e}");
+        process::exit(1);
+    }
 
-    if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    if test && !watch {
+        eprintln!("The `--test` flag can only be used together with `--watch`.");
+        process::exit(1);
     }
 
-    cmd_args.push("--".to_string());
+    if (yes || dry_run) && submit_part.is_none() {
+        eprintln!("The `--yes` and `--dry-run` flags can only be used together with `--submit`.");
+        process::exit(1);
+    }
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    if watch && submit_part.is_some() {
+        eprintln!("The `--submit` flag cannot be used together with `--watch`.");
+        process::exit(1);
+    }
+
+    if watch {
+        let cmd_args = if test {
+            build_test_args(day, options.release)
+        } else {
+            build_args(day, options, None)
+        };
+        watch::watch(day, &cmd_args, test);
+    }
+
+    let mut cmd_args = build_args(day, options, submit_part);
+
+    if let Some(part) = submit_part {
+        let examples = run_example_tests(day, part, options.release);
+        cmd_args.extend(["--examples".into(), examples.to_string()]);
+
+        if yes {
+            cmd_args.push("--yes".into());
+        }
+        if dry_run {
+            cmd_args.push("--dry-run".into());
+        }
     }
 
     let mut cmd = Command::new("cargo")
@@ -30,5 +62,55 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    // forward the exit code, e.g. the verdict of a submission.
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+fn build_args(day: Day, options: BuildOptions, submit_part: Option<u8>) -> Vec<String> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+    cmd_args.extend(options.cargo_args());
+
+    cmd_args.push("--".to_string());
+
+    if let Some(submit_part) = submit_part {
+        cmd_args.push("--submit".to_string());
+        cmd_args.push(submit_part.to_string());
+    }
+
+    cmd_args
+}
+
+/// Run the example tests of `part`, i.e. the tests whose name contains `part_one` or `part_two`.
+fn run_example_tests(day: Day, part: u8, release: bool) -> ExampleStatus {
+    let filter = if part == 1 { "part_one" } else { "part_two" };
+
+    println!("Running example tests for part {part}...");
+
+    let mut cmd_args = build_test_args(day, release);
+    cmd_args.extend(["--quiet".into(), filter.into()]);
+
+    let Ok(output) = Command::new("cargo").args(&cmd_args).output() else {
+        return ExampleStatus::Failed;
+    };
+
+    if !output.status.success() {
+        ExampleStatus::Failed
+    } else if String::from_utf8_lossy(&output.stdout).contains("test result: ok. 0 passed") {
+        ExampleStatus::Missing
+    } else {
+        ExampleStatus::Passed
+    }
+}
+
+fn build_test_args(day: Day, release: bool) -> Vec<String> {
+    let mut cmd_args = vec!["test".to_string(), "--bin".to_string(), day.to_string()];
+
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+
+    cmd_args
 }
@@ -1,10 +1,37 @@
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process;
 
+use crate::template::answers::Answers;
+use crate::template::export::{ExportFormat, export};
+use crate::template::history::{History, HistoryEntry};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{BenchConfig, BuildOptions, Day, all_days, get_year, readme_benchmarks};
+
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    history: bool,
+    export_to: Option<(ExportFormat, PathBuf)>,
+    options: BuildOptions,
+    bench: BenchConfig,
+) {
+    if let Err(e) = options.validate().and_then(|()| bench.validate()) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    if history {
+        let Some(day) = day else {
+            eprintln!("The `--history` flag requires a day. Format: cargo time --history 1");
+            process::exit(1);
+        };
+        History::read_from_file().print_day(day);
+        return;
+    }
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,14 +48,36 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // benchmarks are always run with optimizations.
+    let options = BuildOptions {
+        release: true,
+        ..options
+    };
+    let timings = run_multi(&days_to_run, options, Some(&bench), Path::new(".")).unwrap();
+    let merged_timings = stored_timings.merge(&timings);
+
+    if let Some((format, path)) = export_to {
+        println!();
+        match export(&merged_timings, format, &path, get_year()) {
+            Ok(()) => {
+                println!("Exported benchmarks to \"{}\".", path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to export benchmarks: {e}");
+            }
+        }
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
+        if let Err(e) = HistoryEntry::new(timings.clone(), options.profile_name()).append_to_file()
+        {
+            eprintln!("Failed to append benchmark history: {e}");
+        }
+
         merged_timings.store_file().unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(merged_timings, &Answers::read_from_file()) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -0,0 +1,281 @@
+//! Compares the performance of the working tree with another git revision.
+//! The revision is checked out into a temporary git worktree, and both are benched in alternating rounds
+//! with identical settings, so that drift of the machine affects both sides equally.
+use std::{
+    collections::HashSet,
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::{self, Command},
+    time::Duration,
+};
+
+use crate::template::run_multi::run_multi;
+use crate::template::timings::Timings;
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, BenchConfig, BuildOptions, Day, all_days, format_columns,
+};
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub enum Error {
+    Git(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Git(e) => write!(f, "git: {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A temporary checkout of a revision, removed again when dropped.
+struct Worktree {
+    path: PathBuf,
+}
+
+impl Worktree {
+    fn create(rev: &str) -> Result<Self, Error> {
+        let path = env::temp_dir().join(format!("aoc-compare-{}", process::id()));
+
+        let output = Command::new("git")
+            .args(["worktree", "add", "--detach"])
+            .arg(&path)
+            .arg(rev)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(Error::Git(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        Ok(Worktree { path })
+    }
+
+    /// Puzzle inputs are usually not committed, copy the ones that are missing from the revision.
+    fn copy_inputs(&self) -> Result<(), Error> {
+        let source = Path::new("data").join("inputs");
+        let target = self.path.join("data").join("inputs");
+        fs::create_dir_all(&target)?;
+
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            let destination = target.join(entry.file_name());
+            if entry.file_type()?.is_file() && !destination.exists() {
+                fs::copy(entry.path(), destination)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let _ = Command::new("git")
+            .args(["worktree", "remove", "--force"])
+            .arg(&self.path)
+            .output();
+    }
+}
+
+/// Whether the working tree is faster or slower than the revision.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Change {
+    Faster,
+    Slower,
+    /// The samples of both sides overlap.
+    Noise,
+}
+
+impl Change {
+    /// A change is considered significant if the ranges of both sides do not overlap.
+    /// Returns `None` if there are not enough samples to tell.
+    fn classify(base: &[f64], head: &[f64]) -> Option<Self> {
+        if base.len() < 2 || head.len() < 2 {
+            return None;
+        }
+
+        let min = |x: &[f64]| x.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = |x: &[f64]| x.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        Some(if max(head) < min(base) {
+            Change::Faster
+        } else if min(head) > max(base) {
+            Change::Slower
+        } else {
+            Change::Noise
+        })
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Change::Faster => "faster",
+            Change::Slower => "slower",
+            Change::Noise => "~",
+        }
+    }
+}
+
+fn mean(samples: &[f64]) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let len = samples.len() as f64;
+    samples.iter().sum::<f64>() / len
+}
+
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.1?}")
+}
+
+fn format_delta(base: f64, head: f64) -> String {
+    if base == 0_f64 {
+        return "-".into();
+    }
+    format!("{:+.1}%", (head - base) / base * 100_f64)
+}
+
+/// Collect the timings of `part` of `day` across all rounds.
+fn samples(runs: &[Timings], day: Day, part: u8) -> Vec<f64> {
+    runs.iter()
+        .filter_map(|timings| timings.data.iter().find(|t| t.day == day))
+        .filter_map(|timing| timing.part_nanos(part))
+        .collect()
+}
+
+fn construct_table(rev: &str, base: &[Timings], head: &[Timings]) -> Vec<String> {
+    let header = ["Day", "Part", rev, "Working tree", "Delta", ""].map(String::from);
+
+    let mut rows: Vec<Vec<String>> = vec![];
+
+    for day in all_days() {
+        for part in 1..=2 {
+            let base_samples = samples(base, day, part);
+            let head_samples = samples(head, day, part);
+
+            if base_samples.is_empty() || head_samples.is_empty() {
+                continue;
+            }
+
+            let (base_mean, head_mean) = (mean(&base_samples), mean(&head_samples));
+
+            rows.push(vec![
+                day.to_string(),
+                part.to_string(),
+                format_nanos(base_mean),
+                format_nanos(head_mean),
+                format_delta(base_mean, head_mean),
+                Change::classify(&base_samples, &head_samples)
+                    .map_or("", Change::label)
+                    .into(),
+            ]);
+        }
+    }
+
+    format_columns(&header, &rows)
+}
+
+/// Bench `days` in the working tree and in `rev` for `rounds` rounds each, then print a comparison.
+pub fn compare(
+    rev: &str,
+    days: &HashSet<Day>,
+    options: BuildOptions,
+    bench: &BenchConfig,
+    rounds: usize,
+) -> Result<(), Error> {
+    let worktree = Worktree::create(rev)?;
+    worktree.copy_inputs()?;
+
+    let mut base: Vec<Timings> = vec![];
+    let mut head: Vec<Timings> = vec![];
+
+    for round in 1..=rounds {
+        println!("{ANSI_BOLD}Round {round}/{rounds}: {rev}{ANSI_RESET}\n");
+        base.extend(run_multi(days, options, Some(bench), &worktree.path));
+
+        println!("\n{ANSI_BOLD}Round {round}/{rounds}: working tree{ANSI_RESET}\n");
+        head.extend(run_multi(days, options, Some(bench), Path::new(".")));
+        println!();
+    }
+
+    let table = construct_table(rev, &base, &head);
+    if table.len() == 1 {
+        println!("No days were solved in both `{rev}` and the working tree.");
+        return Ok(());
+    }
+
+    println!("{ANSI_BOLD}Comparison{ANSI_RESET}");
+    println!("------");
+    for line in table {
+        println!("{line}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Change, construct_table, format_delta};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn get_timings(part_1: &str) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some(part_1.into()),
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn classifies_changes() {
+        assert_eq!(
+            Change::classify(&[10.0, 12.0], &[5.0, 6.0]),
+            Some(Change::Faster)
+        );
+        assert_eq!(
+            Change::classify(&[10.0, 12.0], &[13.0, 14.0]),
+            Some(Change::Slower)
+        );
+        assert_eq!(
+            Change::classify(&[10.0, 12.0], &[11.0, 14.0]),
+            Some(Change::Noise)
+        );
+        assert_eq!(Change::classify(&[10.0], &[5.0]), None);
+    }
+
+    #[test]
+    fn formats_deltas() {
+        assert_eq!(format_delta(200.0, 150.0), "-25.0%");
+        assert_eq!(format_delta(100.0, 110.0), "+10.0%");
+        assert_eq!(format_delta(0.0, 10.0), "-");
+    }
+
+    #[test]
+    fn constructs_comparison_table() {
+        let base = [get_timings("2.0ms"), get_timings("2.2ms")];
+        let head = [get_timings("1.0ms"), get_timings("1.2ms")];
+        let table = construct_table("main", &base, &head);
+        assert_eq!(
+            table,
+            [
+                "Day  Part  main   Working tree  Delta",
+                "01   1     2.1ms  1.1ms         -47.6%  faster",
+            ]
+        );
+    }
+}
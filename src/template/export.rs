@@ -0,0 +1,402 @@
+//! Module that exports benchmark timings to standalone report files.
+use std::{fmt::Write, fs, io, path::Path, str::FromStr};
+
+use crate::template::Day;
+use crate::template::readme_benchmarks::{construct_timings_table, get_puzzle_url};
+use crate::template::timings::{CpuStats, MemoryStats, Timing, Timings};
+
+const BAR_HEIGHT: usize = 14;
+const BAR_GAP: usize = 4;
+const DAY_GAP: usize = 12;
+const LABEL_WIDTH: usize = 60;
+const CHART_WIDTH: usize = 480;
+const PART_COLORS: [&str; 2] = ["#5b8def", "#f0a33a"];
+
+/// File formats supported by `cargo time --export`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Markdown,
+    Html,
+}
+
+impl FromStr for ExportFormat {
+    type Err = ExportFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "html" => Ok(ExportFormat::Html),
+            _ => Err(ExportFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`ExportFormat`].
+#[derive(Debug)]
+pub struct ExportFormatFromStrError;
+
+impl std::error::Error for ExportFormatFromStrError {}
+
+impl std::fmt::Display for ExportFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `csv`, `md` or `html`")
+    }
+}
+
+/// Write `timings` to `path` in the given format.
+pub fn export(
+    timings: &Timings,
+    format: ExportFormat,
+    path: &Path,
+    year: Option<u16>,
+) -> Result<(), io::Error> {
+    let content = match format {
+        ExportFormat::Csv => to_csv(timings),
+        ExportFormat::Markdown => to_markdown(timings, year),
+        ExportFormat::Html => to_html(timings, year),
+    };
+    fs::write(path, content)
+}
+
+fn format_nanos(nanos: Option<f64>) -> String {
+    nanos.map_or_else(String::new, |x| format!("{x:.0}"))
+}
+
+fn title(year: Option<u16>) -> String {
+    match year {
+        Some(year) => format!("Advent of Code {year} Benchmarks"),
+        None => "Advent of Code Benchmarks".into(),
+    }
+}
+
+/// The memory and CPU stats of a part, in the order of [`csv_stats_header`]. Cells are empty for stats that
+/// were not measured.
+fn csv_stats(memory: Option<MemoryStats>, cpu: Option<CpuStats>) -> [String; 7] {
+    let cell = |value: Option<u64>| value.map_or_else(String::new, |x| x.to_string());
+    [
+        cell(memory.map(|m| m.peak_bytes)),
+        cell(memory.map(|m| m.total_bytes)),
+        cell(memory.map(|m| m.total_blocks)),
+        cell(cpu.map(|c| c.cpu_nanos)),
+        cell(cpu.and_then(|c| c.instructions)),
+        cell(cpu.and_then(|c| c.cycles)),
+        cell(cpu.and_then(|c| c.cache_misses)),
+    ]
+}
+
+fn csv_stats_header(part: u8) -> String {
+    [
+        "peak_bytes",
+        "total_bytes",
+        "allocations",
+        "cpu_nanos",
+        "instructions",
+        "cycles",
+        "cache_misses",
+    ]
+    .map(|column| format!("part_{part}_{column}"))
+    .join(",")
+}
+
+fn to_csv(timings: &Timings) -> String {
+    let mut lines = vec![format!(
+        "day,part_1,part_1_nanos,part_2,part_2_nanos,total_nanos,{},{}",
+        csv_stats_header(1),
+        csv_stats_header(2)
+    )];
+
+    for timing in &timings.data {
+        lines.push(format!(
+            "{},{},{},{},{},{:.0},{},{}",
+            timing.day,
+            timing.part_1.as_deref().unwrap_or_default(),
+            format_nanos(timing.part_nanos(1)),
+            timing.part_2.as_deref().unwrap_or_default(),
+            format_nanos(timing.part_nanos(2)),
+            timing.total_nanos,
+            csv_stats(timing.part_1_memory, timing.part_1_cpu).join(","),
+            csv_stats(timing.part_2_memory, timing.part_2_cpu).join(",")
+        ));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Reports can be saved anywhere, so days link to their puzzle instead of the solution in this repository.
+fn day_label(day: Day, year: Option<u16>) -> String {
+    match year {
+        Some(year) => format!("[Day {}]({})", day.into_inner(), get_puzzle_url(day, year)),
+        None => format!("Day {}", day.into_inner()),
+    }
+}
+
+fn to_markdown(timings: &Timings, year: Option<u16>) -> String {
+    let mut lines = vec![format!("# {}", title(year)), String::new()];
+    lines.extend(construct_timings_table(timings, |day| day_label(day, year)));
+    lines.push(String::new());
+    lines.push(format!("**Total: {:.2}ms**", timings.total_millis()));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Render a horizontal bar chart with one group of bars (part 1 and part 2) per day.
+fn render_chart(timings: &Timings) -> String {
+    let max_nanos = timings
+        .data
+        .iter()
+        .flat_map(|t| [t.part_nanos(1), t.part_nanos(2)])
+        .flatten()
+        .fold(0_f64, f64::max);
+
+    let group_height = 2 * BAR_HEIGHT + BAR_GAP + DAY_GAP;
+    let height = timings.data.len() * group_height;
+    let width = LABEL_WIDTH + CHART_WIDTH + 100;
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" font-family="sans-serif" font-size="12">"#
+    );
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        let y = i * group_height;
+        let _ = write!(
+            svg,
+            r#"<text x="0" y="{}">Day {}</text>"#,
+            y + BAR_HEIGHT + BAR_GAP / 2 + 4,
+            timing.day
+        );
+
+        for part in 1..=2 {
+            let Some(nanos) = timing.part_nanos(part) else {
+                continue;
+            };
+
+            let bar_y = y + (usize::from(part) - 1) * (BAR_HEIGHT + BAR_GAP);
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let bar_width = if max_nanos > 0_f64 {
+                ((nanos / max_nanos) * CHART_WIDTH as f64).ceil() as usize
+            } else {
+                0
+            };
+            let label = match part {
+                1 => timing.part_1.as_deref(),
+                _ => timing.part_2.as_deref(),
+            }
+            .unwrap_or_default();
+
+            let _ = write!(
+                svg,
+                r#"<rect x="{LABEL_WIDTH}" y="{bar_y}" width="{bar_width}" height="{BAR_HEIGHT}" fill="{}"><title>Part {part}: {}</title></rect><text x="{}" y="{}">{}</text>"#,
+                PART_COLORS[usize::from(part) - 1],
+                escape_html(label),
+                LABEL_WIDTH + bar_width + 4,
+                bar_y + BAR_HEIGHT - 3,
+                escape_html(label),
+            );
+        }
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// The memory and CPU cells of a row in the HTML table, empty for timings measured without them. Columns are
+/// only rendered if any timing has the stats.
+fn html_stats(timing: &Timing, has_memory: bool, has_cpu: bool) -> Vec<String> {
+    let mut cells = vec![];
+    if has_memory {
+        cells.extend(
+            [timing.part_1_memory, timing.part_2_memory]
+                .map(|m| m.map(|m| m.to_string()).unwrap_or_default()),
+        );
+    }
+    if has_cpu {
+        cells.extend(
+            [timing.part_1_cpu, timing.part_2_cpu]
+                .map(|c| c.map(|c| c.to_string()).unwrap_or_default()),
+        );
+    }
+    cells
+}
+
+fn to_html(timings: &Timings, year: Option<u16>) -> String {
+    let title = escape_html(&title(year));
+    let has_memory = timings.has_memory();
+    let has_cpu = timings.has_cpu();
+
+    let mut rows = String::new();
+    for timing in &timings.data {
+        let _ = write!(
+            rows,
+            "<tr><td>Day {}</td><td>{}</td><td>{}</td>",
+            timing.day,
+            escape_html(timing.part_1.as_deref().unwrap_or("-")),
+            escape_html(timing.part_2.as_deref().unwrap_or("-"))
+        );
+        for cell in html_stats(timing, has_memory, has_cpu) {
+            let _ = write!(rows, "<td>{}</td>", escape_html(&cell));
+        }
+        rows.push_str("</tr>");
+    }
+
+    let mut stats_header = String::new();
+    if has_memory {
+        stats_header.push_str("<th>Memory (Part 1)</th><th>Memory (Part 2)</th>");
+    }
+    if has_cpu {
+        stats_header.push_str("<th>CPU (Part 1)</th><th>CPU (Part 2)</th>");
+    }
+    let stats_footer = "<th></th>".repeat(2 * (usize::from(has_memory) + usize::from(has_cpu)));
+
+    [
+        "<!DOCTYPE html>".to_string(),
+        "<html>".into(),
+        "<head>".into(),
+        r#"<meta charset="utf-8">"#.into(),
+        format!("<title>{title}</title>"),
+        "<style>body { font-family: sans-serif; margin: 2em; } table { border-collapse: collapse; margin-bottom: 2em; } td, th { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: center; }</style>".into(),
+        "</head>".into(),
+        "<body>".into(),
+        format!("<h1>{title}</h1>"),
+        "<table>".into(),
+        format!("<thead><tr><th>Day</th><th>Part 1</th><th>Part 2</th>{stats_header}</tr></thead>"),
+        format!("<tbody>{rows}</tbody>"),
+        format!(
+            "<tfoot><tr><th>Total</th><th>{:.2}ms</th><th>{:.2}ms</th>{stats_footer}</tr></tfoot>",
+            timings.part_millis(1),
+            timings.part_millis(2)
+        ),
+        "</table>".into(),
+        render_chart(timings),
+        format!("<p><strong>Total: {:.2}ms</strong></p>", timings.total_millis()),
+        "</body>".into(),
+        "</html>".into(),
+        String::new(),
+    ]
+    .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExportFormat, to_csv, to_html, to_markdown};
+    use crate::{
+        day,
+        template::timings::{CpuStats, MemoryStats, Timing, Timings},
+    };
+    use std::str::FromStr;
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("10ms".into()),
+                    part_2: Some("20µs".into()),
+                    total_nanos: 10_020_000_f64,
+                    ..Default::default()
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("30ms".into()),
+                    total_nanos: 30_000_000_f64,
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(ExportFormat::from_str("csv").unwrap(), ExportFormat::Csv);
+        assert_eq!(
+            ExportFormat::from_str("md").unwrap(),
+            ExportFormat::Markdown
+        );
+        assert_eq!(ExportFormat::from_str("HTML").unwrap(), ExportFormat::Html);
+        assert!(ExportFormat::from_str("pdf").is_err());
+    }
+
+    /// Mock timings where day 1 was measured with memory stats and CPU time, and day 2 without them.
+    fn get_mock_timings_with_stats() -> Timings {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_memory = Some(MemoryStats {
+            peak_bytes: 2048,
+            total_bytes: 4096,
+            total_blocks: 3,
+        });
+        timings.data[0].part_2_cpu = Some(CpuStats {
+            cpu_nanos: 20_000,
+            instructions: Some(1000),
+            cycles: None,
+            cache_misses: None,
+        });
+        timings
+    }
+
+    #[test]
+    fn exports_csv() {
+        let stats_header = "part_1_peak_bytes,part_1_total_bytes,part_1_allocations,part_1_cpu_nanos,part_1_instructions,part_1_cycles,part_1_cache_misses,part_2_peak_bytes,part_2_total_bytes,part_2_allocations,part_2_cpu_nanos,part_2_instructions,part_2_cycles,part_2_cache_misses";
+        let expected = [
+            format!("day,part_1,part_1_nanos,part_2,part_2_nanos,total_nanos,{stats_header}"),
+            "01,10ms,10000000,20µs,20000,10020000,,,,,,,,,,,,,,".into(),
+            "02,30ms,30000000,,,30000000,,,,,,,,,,,,,,".into(),
+            String::new(),
+        ]
+        .join("\n");
+        assert_eq!(to_csv(&get_mock_timings()), expected);
+
+        let csv = to_csv(&get_mock_timings_with_stats());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[1],
+            "01,10ms,10000000,20µs,20000,10020000,2048,4096,3,,,,,,,,20000,1000,,"
+        );
+        assert_eq!(lines[2], "02,30ms,30000000,,,30000000,,,,,,,,,,,,,,");
+    }
+
+    #[test]
+    fn exports_markdown() {
+        let md = to_markdown(&get_mock_timings(), Some(2025));
+        assert!(md.starts_with("# Advent of Code 2025 Benchmarks\n"));
+        assert!(md.contains("| **Total** | `40.00ms` | `0.02ms` |"));
+        assert!(md.contains("**Total: 40.02ms**"));
+        assert!(md.contains("| [Day 1](https://adventofcode.com/2025/day/1) | `10ms` |"));
+        assert!(!md.contains("./src/bin"));
+
+        let md = to_markdown(&get_mock_timings(), None);
+        assert!(md.contains("| Day 1 | `10ms` |"));
+    }
+
+    #[test]
+    fn exports_html_with_chart() {
+        let html = to_html(&get_mock_timings(), None);
+        assert!(html.contains("<svg"));
+        assert_eq!(html.matches("<rect").count(), 3);
+        assert!(html.contains("<td>Day 02</td><td>30ms</td><td>-</td></tr>"));
+        assert!(!html.contains("Memory"));
+    }
+
+    #[test]
+    fn exports_html_with_stats() {
+        let html = to_html(&get_mock_timings_with_stats(), None);
+        assert!(html.contains(
+            "<thead><tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Memory (Part 1)</th><th>Memory (Part 2)</th><th>CPU (Part 1)</th><th>CPU (Part 2)</th></tr></thead>"
+        ));
+        assert!(html.contains(
+            "<tr><td>Day 01</td><td>10ms</td><td>20µs</td><td>peak 2.0 KiB, 4.0 KiB in 3 allocations</td><td></td><td></td><td>cpu 20.0µs, 1000 instructions</td></tr>"
+        ));
+        assert!(html.contains(
+            "<tr><td>Day 02</td><td>30ms</td><td>-</td><td></td><td></td><td></td><td></td></tr>"
+        ));
+        assert!(html.contains("<th>0.02ms</th><th></th><th></th><th></th><th></th></tr></tfoot>"));
+    }
+}
@@ -0,0 +1,428 @@
+//! Append-only log of benchmark runs.
+//! Every stored `cargo time` run is appended as a single JSON line, tagged with the time of the run,
+//! the git commit it was run on, the build profile and some information about the machine.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    process::Command,
+    str::FromStr,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::{CpuStats, Timing, Timings};
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, format_columns};
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// Describes the machine a benchmark was run on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Machine {
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+    pub hostname: Option<String>,
+}
+
+impl Machine {
+    /// Collect information about the current machine.
+    pub fn current() -> Self {
+        Machine {
+            os: env::consts::OS.into(),
+            arch: env::consts::ARCH.into(),
+            cpus: thread::available_parallelism().map_or(1, std::num::NonZero::get),
+            hostname: env::var("HOSTNAME")
+                .or_else(|_| env::var("COMPUTERNAME"))
+                .ok()
+                .or_else(|| fs::read_to_string("/etc/hostname").ok())
+                .map(|x| x.trim().to_string())
+                .filter(|x| !x.is_empty()),
+        }
+    }
+}
+
+/// A single benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the git commit, suffixed with `-dirty` if the working tree had changes.
+    pub commit: Option<String>,
+    pub profile: String,
+    pub machine: Machine,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Create an entry for `timings` that were just measured with `profile`.
+    pub fn new(timings: Timings, profile: &str) -> Self {
+        HistoryEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            commit: get_commit(),
+            profile: profile.into(),
+            machine: Machine::current(),
+            timings,
+        }
+    }
+
+    /// Append the entry to the history file.
+    pub fn append_to_file(&self) -> Result<(), Error> {
+        let line = JsonValue::from(self)
+            .stringify()
+            .map_err(|e| Error::other(e.to_string()))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(HISTORY_FILE_PATH)?;
+
+        writeln!(file, "{line}")
+    }
+}
+
+/// All benchmark runs, in the order they were recorded.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Read the history file. Lines that cannot be parsed are skipped with a warning.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(HISTORY_FILE_PATH)
+            .map(|s| History::parse(&s))
+            .unwrap_or_default()
+    }
+
+    fn parse(s: &str) -> Self {
+        let entries = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .filter_map(|(i, line)| match HistoryEntry::try_from(line) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    eprintln!("Skipping line {} of benchmark history: {e}", i + 1);
+                    None
+                }
+            })
+            .collect();
+
+        History { entries }
+    }
+
+    /// Iterate over the runs that include a timing for `day`.
+    pub fn for_day(&self, day: Day) -> impl Iterator<Item = (&HistoryEntry, &Timing)> {
+        self.entries.iter().filter_map(move |entry| {
+            entry
+                .timings
+                .data
+                .iter()
+                .find(|t| t.day == day)
+                .map(|t| (entry, t))
+        })
+    }
+
+    /// Print how the timings of `day` evolved across runs.
+    pub fn print_day(&self, day: Day) {
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        // CPU time is less noisy than wall time on shared machines, show it when it was measured.
+        let has_cpu = self
+            .for_day(day)
+            .any(|(_, t)| t.part_1_cpu.is_some() || t.part_2_cpu.is_some());
+
+        let format_cpu = |cpu: Option<CpuStats>| {
+            cpu.map_or_else(
+                || "-".into(),
+                |c| format!("{:.1?}", Duration::from_nanos(c.cpu_nanos)),
+            )
+        };
+
+        let rows: Vec<Vec<String>> = self
+            .for_day(day)
+            .map(|(entry, timing)| {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let total = Duration::from_nanos(timing.total_nanos as u64);
+                let mut row = vec![
+                    format_timestamp(entry.timestamp),
+                    entry.commit.clone().unwrap_or_else(|| "-".into()),
+                    entry.profile.clone(),
+                    timing.part_1.clone().unwrap_or_else(|| "-".into()),
+                    timing.part_2.clone().unwrap_or_else(|| "-".into()),
+                    format!("{total:.1?}"),
+                ];
+                if has_cpu {
+                    row.push(format_cpu(timing.part_1_cpu));
+                    row.push(format_cpu(timing.part_2_cpu));
+                }
+                row
+            })
+            .collect();
+
+        if rows.is_empty() {
+            println!("No benchmark history. Run `cargo time {day} --store` to record one.");
+            return;
+        }
+
+        let mut header: Vec<String> = ["Date", "Commit", "Profile", "Part 1", "Part 2", "Total"]
+            .map(String::from)
+            .to_vec();
+        if has_cpu {
+            header.extend(["CPU 1".into(), "CPU 2".into()]);
+        }
+
+        for line in format_columns(&header, &rows) {
+            println!("{line}");
+        }
+    }
+}
+
+fn get_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|o| o.status.success() && !o.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
+
+/// Format a unix timestamp as `YYYY-MM-DD HH:MM` (UTC).
+fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let seconds = timestamp % 86_400;
+
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Machine> for JsonValue {
+    fn from(value: &Machine) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("os".into(), JsonValue::String(value.os.clone()));
+        map.insert("arch".into(), JsonValue::String(value.arch.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("cpus".into(), JsonValue::Number(value.cpus as f64));
+        map.insert(
+            "hostname".into(),
+            value
+                .hostname
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Machine {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected machine to be a JSON object.")?;
+
+        let get_string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected machine.{key} to be a string."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let cpus = json
+            .get("cpus")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as usize)
+            .ok_or("Expected machine.cpus to be a number.")?;
+
+        Ok(Machine {
+            os: get_string("os")?,
+            arch: get_string("arch")?,
+            cpus,
+            hostname: json
+                .get("hostname")
+                .and_then(|v| v.get::<String>())
+                .cloned(),
+        })
+    }
+}
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("machine".into(), JsonValue::from(&value.machine));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let map = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected history entry to be an object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = map
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let commit = map.get("commit").and_then(|v| v.get::<String>()).cloned();
+
+        let profile = map
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("Expected entry.profile to be a string.")?;
+
+        let machine = map
+            .get("machine")
+            .ok_or("Expected entry to have key `machine`.")
+            .map(Machine::try_from)??;
+
+        Ok(HistoryEntry {
+            timestamp,
+            commit,
+            profile,
+            machine,
+            timings: Timings::try_from(&json)?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{History, HistoryEntry, Machine, format_timestamp};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+    use tinyjson::JsonValue;
+
+    fn get_mock_entry(timestamp: u64, day: u8, total_nanos: f64) -> HistoryEntry {
+        HistoryEntry {
+            timestamp,
+            commit: Some("abc1234".into()),
+            profile: "release".into(),
+            machine: Machine {
+                os: "linux".into(),
+                arch: "x86_64".into(),
+                cpus: 8,
+                hostname: None,
+            },
+            timings: Timings {
+                data: vec![Timing {
+                    day: crate::template::Day::new(day).unwrap(),
+                    part_1: Some("10ms".into()),
+                    total_nanos,
+                    ..Default::default()
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = get_mock_entry(1_733_011_200, 1, 1e7);
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        let parsed = HistoryEntry::try_from(line.as_str()).unwrap();
+        assert_eq!(parsed.timestamp, entry.timestamp);
+        assert_eq!(parsed.commit, entry.commit);
+        assert_eq!(parsed.profile, entry.profile);
+        assert_eq!(parsed.machine, entry.machine);
+        assert_eq!(parsed.timings.data.len(), 1);
+        assert_eq!(parsed.timings.data[0].part_1, Some("10ms".into()));
+    }
+
+    #[test]
+    fn parses_history_and_skips_invalid_lines() {
+        let lines = [
+            JsonValue::from(&get_mock_entry(1, 1, 1e7))
+                .stringify()
+                .unwrap(),
+            "not json".into(),
+            String::new(),
+            JsonValue::from(&get_mock_entry(2, 2, 2e7))
+                .stringify()
+                .unwrap(),
+            JsonValue::from(&get_mock_entry(3, 1, 3e7))
+                .stringify()
+                .unwrap(),
+        ];
+        let history = History::parse(&lines.join("\n"));
+        assert_eq!(history.entries.len(), 3);
+
+        let day_1: Vec<u64> = history.for_day(day!(1)).map(|(e, _)| e.timestamp).collect();
+        assert_eq!(day_1, vec![1, 3]);
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_011_200), "2024-12-01 00:00");
+        assert_eq!(format_timestamp(1_709_210_096), "2024-02-29 12:34");
+    }
+}
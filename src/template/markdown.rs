@@ -0,0 +1,477 @@
+//! Minimal markdown renderer for puzzle descriptions saved by aoc-cli.
+//! Supports the subset of markdown that aoc-cli produces: headings, paragraphs, lists,
+//! code blocks, inline code, emphasis and links.
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+const ANSI_CODE: &str = "\x1b[36m";
+
+/// Returns the section of a puzzle description that belongs to `part`.
+/// Returns [`None`] if the requested part is not part of the description (yet).
+#[must_use]
+pub fn select_part(markdown: &str, part: u8) -> Option<&str> {
+    let mut offset = 0;
+    let mut part_two_start = None;
+
+    for line in markdown.split_inclusive('\n') {
+        if line.contains("--- Part Two ---") {
+            part_two_start = Some(offset);
+            break;
+        }
+        offset += line.len();
+    }
+
+    match (part, part_two_start) {
+        (1, Some(i)) => Some(&markdown[..i]),
+        (1, None) => Some(markdown),
+        (2, Some(i)) => Some(&markdown[i..]),
+        _ => None,
+    }
+}
+
+/// Render `markdown` with ANSI formatting, wrapping text to `width` columns.
+#[must_use]
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut renderer = Renderer {
+        width,
+        out: vec![],
+        paragraph: None,
+    };
+
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            renderer.flush();
+            i += 1;
+            while i < lines.len() && !lines[i].trim().starts_with("```") {
+                renderer.code_line(lines[i]);
+                i += 1;
+            }
+            renderer.blank();
+        } else if trimmed.is_empty() {
+            renderer.flush();
+            renderer.blank();
+        } else if let Some(level) = heading_level(lines.get(i + 1).copied()) {
+            // setext heading: a line of text underlined with `===` or `---`.
+            renderer.heading(trimmed, level);
+            i += 1;
+        } else if trimmed.starts_with('#') {
+            let text = trimmed.trim_start_matches('#');
+            renderer.heading(text.trim(), trimmed.len() - text.len());
+        } else if is_rule(trimmed) && renderer.paragraph.is_none() {
+            renderer.flush();
+            renderer.out.push("─".repeat(width));
+        } else if let Some((marker, text)) = list_item(trimmed) {
+            renderer.flush();
+            renderer.paragraph = Some(Paragraph {
+                first_prefix: format!("  {marker} "),
+                rest_prefix: " ".repeat(marker.chars().count() + 3),
+                text: text.to_string(),
+                is_list: true,
+            });
+        } else if line.starts_with("    ") && renderer.paragraph.is_none() {
+            renderer.code_line(&line[4..]);
+        } else {
+            renderer.text(trimmed);
+        }
+
+        i += 1;
+    }
+
+    renderer.flush();
+
+    while renderer.out.last().is_some_and(String::is_empty) {
+        renderer.out.pop();
+    }
+
+    renderer.out.join("\n")
+}
+
+struct Paragraph {
+    first_prefix: String,
+    rest_prefix: String,
+    text: String,
+    is_list: bool,
+}
+
+struct Renderer {
+    width: usize,
+    out: Vec<String>,
+    paragraph: Option<Paragraph>,
+}
+
+impl Renderer {
+    fn text(&mut self, text: &str) {
+        match &mut self.paragraph {
+            Some(paragraph) => {
+                paragraph.text.push(' ');
+                paragraph.text.push_str(text);
+            }
+            None => {
+                self.paragraph = Some(Paragraph {
+                    first_prefix: String::new(),
+                    rest_prefix: String::new(),
+                    text: text.to_string(),
+                    is_list: false,
+                });
+            }
+        }
+    }
+
+    fn heading(&mut self, text: &str, level: usize) {
+        self.flush();
+        let text = render_inline(text)
+            .into_iter()
+            .map(|w| w.text)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let style = if level <= 1 {
+            format!("{ANSI_BOLD}{ANSI_UNDERLINE}")
+        } else {
+            ANSI_BOLD.to_string()
+        };
+        self.out.push(format!("{style}{text}{ANSI_RESET}"));
+        self.blank();
+    }
+
+    fn code_line(&mut self, line: &str) {
+        self.flush();
+        self.out.push(format!("    {ANSI_CODE}{line}{ANSI_RESET}"));
+    }
+
+    fn blank(&mut self) {
+        if self.out.last().is_some_and(|l| !l.is_empty()) {
+            self.out.push(String::new());
+        }
+    }
+
+    fn flush(&mut self) {
+        let Some(paragraph) = self.paragraph.take() else {
+            return;
+        };
+
+        let words = render_inline(&paragraph.text);
+        self.out.extend(wrap(
+            &words,
+            self.width,
+            &paragraph.first_prefix,
+            &paragraph.rest_prefix,
+        ));
+
+        if !paragraph.is_list {
+            self.blank();
+        }
+    }
+}
+
+fn heading_level(next_line: Option<&str>) -> Option<usize> {
+    let next_line = next_line?.trim();
+    if next_line.len() >= 3 && next_line.chars().all(|c| c == '=') {
+        Some(1)
+    } else if next_line.len() >= 3 && next_line.chars().all(|c| c == '-') {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+fn is_rule(line: &str) -> bool {
+    let line: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    line.len() >= 3
+        && (line.chars().all(|c| c == '-')
+            || line.chars().all(|c| c == '*')
+            || line.chars().all(|c| c == '_'))
+}
+
+fn list_item(line: &str) -> Option<(String, &str)> {
+    for bullet in ["* ", "- ", "+ "] {
+        if let Some(text) = line.strip_prefix(bullet) {
+            return Some(("•".into(), text.trim_start()));
+        }
+    }
+
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0
+        && let Some(text) = line[digits..].strip_prefix(". ")
+    {
+        return Some((line[..=digits].to_string(), text.trim_start()));
+    }
+
+    None
+}
+
+/// A word of rendered text. `text` may contain ANSI escape codes, `len` is its visible width.
+#[derive(Debug)]
+struct Word {
+    text: String,
+    len: usize,
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+struct Style {
+    bold: bool,
+    code: bool,
+    link: bool,
+}
+
+impl Style {
+    fn codes(self) -> String {
+        let mut s = String::new();
+        if self.bold {
+            s.push_str(ANSI_BOLD);
+        }
+        if self.code {
+            s.push_str(ANSI_CODE);
+        }
+        if self.link {
+            s.push_str(ANSI_UNDERLINE);
+        }
+        s
+    }
+}
+
+#[derive(Default)]
+struct WordBuilder {
+    words: Vec<Word>,
+    text: String,
+    len: usize,
+    style: Style,
+    active: Style,
+}
+
+impl WordBuilder {
+    fn push(&mut self, c: char) {
+        if c.is_whitespace() && !self.style.code {
+            self.end_word();
+            return;
+        }
+
+        if self.active != self.style {
+            if self.active != Style::default() {
+                self.text.push_str(ANSI_RESET);
+            }
+            self.text.push_str(&self.style.codes());
+            self.active = self.style;
+        }
+
+        self.text.push(c);
+        self.len += 1;
+    }
+
+    fn end_word(&mut self) {
+        if self.len == 0 {
+            return;
+        }
+        if self.active != Style::default() {
+            self.text.push_str(ANSI_RESET);
+            self.active = Style::default();
+        }
+        self.words.push(Word {
+            text: std::mem::take(&mut self.text),
+            len: self.len,
+        });
+        self.len = 0;
+    }
+}
+
+fn render_inline(text: &str) -> Vec<Word> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut builder = WordBuilder::default();
+    // positions and lengths of the `*` runs that close open emphasis.
+    let mut closers: Vec<(usize, usize)> = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        match c {
+            '\\' if chars.get(i + 1).is_some_and(char::is_ascii_punctuation) => {
+                builder.push(chars[i + 1]);
+                i += 2;
+            }
+            '`' => {
+                if let Some(len) = chars[i + 1..].iter().position(|&c| c == '`') {
+                    builder.style.code = true;
+                    chars[i + 1..i + 1 + len]
+                        .iter()
+                        .for_each(|&c| builder.push(c));
+                    builder.style.code = false;
+                    i += len + 2;
+                } else {
+                    builder.push(c);
+                    i += 1;
+                }
+            }
+            '*' => {
+                let run = if chars.get(i + 1) == Some(&'*') { 2 } else { 1 };
+                if let Some(index) = closers.iter().position(|&(at, _)| at == i) {
+                    closers.remove(index);
+                } else if let Some(at) = find_emphasis_closer(&chars, i, run) {
+                    closers.push((at, run));
+                } else {
+                    (0..run).for_each(|_| builder.push(c));
+                }
+                builder.style.bold = !closers.is_empty();
+                i += run;
+            }
+            '[' => {
+                if let Some((label_len, link_len)) = find_link(&chars[i..]) {
+                    builder.style.link = true;
+                    chars[i + 1..i + 1 + label_len]
+                        .iter()
+                        .for_each(|&c| builder.push(c));
+                    builder.style.link = false;
+                    i += link_len;
+                } else {
+                    builder.push(c);
+                    i += 1;
+                }
+            }
+            _ => {
+                builder.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    builder.end_word();
+    builder.words
+}
+
+/// For a run of `run` asterisks at `start`, returns the position of the run that closes it. Runs only open
+/// emphasis before non-space text and close it after non-space text, so that e.g. `a * b` stays literal.
+fn find_emphasis_closer(chars: &[char], start: usize, run: usize) -> Option<usize> {
+    let is_text = |c: Option<&char>| c.is_some_and(|c| !c.is_whitespace() && *c != '*');
+    if !is_text(chars.get(start + run)) {
+        return None;
+    }
+
+    (start + run + 1..chars.len()).find(|&at| {
+        chars[at..].iter().take_while(|&&c| c == '*').count() == run && is_text(chars.get(at - 1))
+    })
+}
+
+/// For `[label](url)` at the start of `chars`, returns the length of the label and of the whole link.
+fn find_link(chars: &[char]) -> Option<(usize, usize)> {
+    let label_len = chars.iter().position(|&c| c == ']')? - 1;
+    if chars.get(label_len + 2) != Some(&'(') {
+        return None;
+    }
+    let url_len = chars[label_len + 3..].iter().position(|&c| c == ')')?;
+    Some((label_len, label_len + url_len + 4))
+}
+
+fn wrap(words: &[Word], width: usize, first_prefix: &str, rest_prefix: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first_prefix.to_string();
+    let mut line_len = first_prefix.chars().count();
+    let mut is_empty = true;
+
+    for word in words {
+        if !is_empty && line_len + 1 + word.len > width {
+            lines.push(std::mem::replace(&mut line, rest_prefix.to_string()));
+            line_len = rest_prefix.chars().count();
+            is_empty = true;
+        }
+
+        if !is_empty {
+            line.push(' ');
+            line_len += 1;
+        }
+
+        line.push_str(&word.text);
+        line_len += word.len;
+        is_empty = false;
+    }
+
+    if !is_empty {
+        lines.push(line);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ANSI_UNDERLINE, render, select_part};
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    const PUZZLE: &str = "\\--- Day 1: Secret Entrance ---\n----------\n\nThe dial starts by pointing at *50*.\n\n```\nL68\nL30\n```\n\n\\--- Part Two ---\n----------\n\nCount every click.\n";
+
+    #[test]
+    fn renders_headings_and_emphasis() {
+        let rendered = render(PUZZLE, 80);
+        assert!(rendered.starts_with(&format!(
+            "{ANSI_BOLD}--- Day 1: Secret Entrance ---{ANSI_RESET}\n\n"
+        )));
+        assert!(rendered.contains(&format!("at {ANSI_BOLD}50{ANSI_RESET}.")));
+    }
+
+    #[test]
+    fn keeps_code_blocks_verbatim() {
+        let rendered = render(PUZZLE, 2);
+        assert!(rendered.contains("L68"));
+        assert!(rendered.contains("L30"));
+    }
+
+    #[test]
+    fn wraps_paragraphs() {
+        let rendered = render("one two three four\nfive six", 10);
+        assert_eq!(rendered, "one two\nthree four\nfive six");
+    }
+
+    #[test]
+    fn wraps_list_items_with_hanging_indent() {
+        let rendered = render("* one two three", 11);
+        assert_eq!(rendered, "  • one two\n    three");
+    }
+
+    #[test]
+    fn renders_links_as_labels() {
+        let rendered = render("see [the example](/2025/day/1) below", 80);
+        assert_eq!(
+            rendered,
+            format!(
+                "see {ANSI_UNDERLINE}the{ANSI_RESET} {ANSI_UNDERLINE}example{ANSI_RESET} below"
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_literal_asterisks() {
+        assert_eq!(render("a * b * c", 80), "a * b * c");
+        assert_eq!(render("2 ** 3", 80), "2 ** 3");
+        assert_eq!(render("*a* b", 80), format!("{ANSI_BOLD}a{ANSI_RESET} b"));
+        assert_eq!(
+            render("**a b** c", 80),
+            format!("{ANSI_BOLD}a{ANSI_RESET} {ANSI_BOLD}b{ANSI_RESET} c")
+        );
+        assert_eq!(
+            render("**a *b* c**", 80),
+            format!("{ANSI_BOLD}a{ANSI_RESET} {ANSI_BOLD}b{ANSI_RESET} {ANSI_BOLD}c{ANSI_RESET}")
+        );
+        assert_eq!(render("*a * b", 80), "*a * b");
+    }
+
+    #[test]
+    fn selects_parts() {
+        assert!(!select_part(PUZZLE, 1).unwrap().contains("Part Two"));
+        assert!(
+            select_part(PUZZLE, 2)
+                .unwrap()
+                .starts_with("\\--- Part Two ---")
+        );
+    }
+
+    #[test]
+    fn handles_locked_part_two() {
+        let part_one = select_part(PUZZLE, 1).unwrap();
+        assert_eq!(select_part(part_one, 1), Some(part_one));
+        assert_eq!(select_part(part_one, 2), None);
+    }
+}
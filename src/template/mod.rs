@@ -1,20 +1,81 @@
 use std::{env, fs};
 
+#[cfg(feature = "count-allocations")]
+pub mod alloc;
 pub mod aoc_cli;
 pub mod commands;
+pub mod perf;
 pub mod runner;
 
+pub use bench_config::BenchConfig;
+pub use build_options::BuildOptions;
 pub use day::*;
+pub use export::ExportFormat;
 
+mod answers;
+mod bench_config;
+mod build_options;
+mod compare;
 mod day;
+mod export;
+mod history;
+mod markdown;
 mod readme_benchmarks;
 mod run_multi;
+mod submissions;
 mod timings;
+mod watch;
+
+// installed here rather than by `solution!`, so that binaries linking several days, like the bench harness,
+// still have a single global allocator.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+// the unit tests of the counting allocator measure an instance of their own, without other tests interfering.
+#[cfg(all(feature = "count-allocations", not(feature = "dhat-heap"), not(test)))]
+#[global_allocator]
+static ALLOC: alloc::CountingAlloc = alloc::CountingAlloc;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Returns the year configured via the `AOC_YEAR` environment variable, if any.
+#[must_use]
+pub fn get_year() -> Option<u16> {
+    match env::var("AOC_YEAR") {
+        Ok(x) => x.parse().ok().or(None),
+        Err(_) => None,
+    }
+}
+
+/// Align `rows` below `header` in columns separated by two spaces.
+#[must_use]
+pub fn format_columns(header: &[String], rows: &[Vec<String>]) -> Vec<String> {
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            std::iter::once(header)
+                .chain(rows.iter().map(Vec::as_slice))
+                .map(|row| row.get(i).map_or(0, |cell| cell.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    std::iter::once(header)
+        .chain(rows.iter().map(Vec::as_slice))
+        .map(|row| {
+            let line: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect();
+            line.join("  ").trim_end().to_string()
+        })
+        .collect()
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
@@ -55,10 +116,6 @@ macro_rules! solution {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
-
         fn main() {
             use $crate::template::runner::*;
             let input = $crate::template::read_file("inputs", DAY);
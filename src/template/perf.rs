@@ -0,0 +1,175 @@
+//! Measures per-thread CPU time and hardware counters around solution runs, and pins benchmarks to a CPU.
+//! Only implemented on Linux with the `cpu-time` feature enabled, where it uses `clock_gettime` and `perf_event_open`.
+//! Hardware counters are optional: on machines that do not expose perf events (e.g. most containers), only CPU time is reported.
+use crate::template::timings::CpuStats;
+
+/// Restrict the calling thread to run on `cpu` only.
+#[cfg(target_os = "linux")]
+pub fn pin_to_cpu(cpu: usize) -> Result<(), String> {
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    if cpu >= 8 * size_of::<libc::cpu_set_t>() {
+        return Err(format!("cpu {cpu} is out of range"));
+    }
+
+    unsafe { libc::CPU_SET(cpu, &mut set) };
+    // pid 0 refers to the calling thread.
+    match unsafe { libc::sched_setaffinity(0, size_of::<libc::cpu_set_t>(), &raw const set) } {
+        0 => Ok(()),
+        _ => Err(std::io::Error::last_os_error().to_string()),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_to_cpu(_cpu: usize) -> Result<(), String> {
+    Err("pinning is only supported on Linux".into())
+}
+
+/// A running measurement. Create it right before the measured code and call [`Counters::stop`] right after.
+pub struct Counters {
+    #[cfg(all(feature = "cpu-time", target_os = "linux"))]
+    start_cpu_nanos: u64,
+    #[cfg(all(feature = "cpu-time", target_os = "linux"))]
+    events: [Option<linux::Event>; 3],
+}
+
+#[cfg(all(feature = "cpu-time", target_os = "linux"))]
+impl Counters {
+    pub fn start() -> Self {
+        let events = [
+            linux::PERF_COUNT_HW_INSTRUCTIONS,
+            linux::PERF_COUNT_HW_CPU_CYCLES,
+            linux::PERF_COUNT_HW_CACHE_MISSES,
+        ]
+        .map(linux::Event::open);
+
+        for event in events.iter().flatten() {
+            event.enable();
+        }
+
+        Counters {
+            start_cpu_nanos: linux::thread_cpu_nanos(),
+            events,
+        }
+    }
+
+    /// Stop measuring and average the results over `samples` runs.
+    pub fn stop(self, samples: u128) -> Option<CpuStats> {
+        let cpu_nanos = linux::thread_cpu_nanos() - self.start_cpu_nanos;
+
+        let [instructions, cycles, cache_misses] = self
+            .events
+            .map(|event| event.and_then(|mut event| event.read()));
+
+        #[allow(clippy::cast_possible_truncation)]
+        let average = |x: u64| (u128::from(x) / samples.max(1)) as u64;
+
+        Some(CpuStats {
+            cpu_nanos: average(cpu_nanos),
+            instructions: instructions.map(average),
+            cycles: cycles.map(average),
+            cache_misses: cache_misses.map(average),
+        })
+    }
+}
+
+#[cfg(not(all(feature = "cpu-time", target_os = "linux")))]
+impl Counters {
+    pub fn start() -> Self {
+        Counters {}
+    }
+
+    pub fn stop(self, _samples: u128) -> Option<CpuStats> {
+        None
+    }
+}
+
+#[cfg(all(feature = "cpu-time", target_os = "linux"))]
+mod linux {
+    use std::fs::File;
+    use std::io::Read;
+    use std::os::fd::{AsRawFd, FromRawFd};
+
+    // see: https://man7.org/linux/man-pages/man2/perf_event_open.2.html
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    pub const PERF_COUNT_HW_CPU_CYCLES: u64 = 0;
+    pub const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    pub const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+
+    const PERF_EVENT_IOC_ENABLE: libc::Ioctl = 0x2400;
+    const PERF_EVENT_IOC_DISABLE: libc::Ioctl = 0x2401;
+    const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+
+    // bit flags of `perf_event_attr`.
+    const DISABLED: u64 = 1 << 0;
+    const EXCLUDE_KERNEL: u64 = 1 << 5;
+    const EXCLUDE_HV: u64 = 1 << 6;
+
+    /// First published version of `struct perf_event_attr` (`PERF_ATTR_SIZE_VER0`), which every kernel accepts.
+    #[repr(C)]
+    #[derive(Default)]
+    struct PerfEventAttr {
+        type_: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    /// A hardware counter of the calling thread, in user space only.
+    pub struct Event(File);
+
+    impl Event {
+        pub fn open(config: u64) -> Option<Self> {
+            let attr = PerfEventAttr {
+                type_: PERF_TYPE_HARDWARE,
+                size: u32::try_from(size_of::<PerfEventAttr>()).ok()?,
+                config,
+                flags: DISABLED | EXCLUDE_KERNEL | EXCLUDE_HV,
+                ..Default::default()
+            };
+
+            // pid 0 and cpu -1 measure the calling thread on any cpu.
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    &raw const attr,
+                    0 as libc::pid_t,
+                    -1 as libc::c_int,
+                    -1 as libc::c_int,
+                    PERF_FLAG_FD_CLOEXEC,
+                )
+            };
+
+            let fd = i32::try_from(fd).ok().filter(|fd| *fd >= 0)?;
+            Some(Event(unsafe { File::from_raw_fd(fd) }))
+        }
+
+        pub fn enable(&self) {
+            unsafe { libc::ioctl(self.0.as_raw_fd(), PERF_EVENT_IOC_ENABLE, 0) };
+        }
+
+        pub fn read(&mut self) -> Option<u64> {
+            unsafe { libc::ioctl(self.0.as_raw_fd(), PERF_EVENT_IOC_DISABLE, 0) };
+            let mut buf = [0_u8; 8];
+            self.0.read_exact(&mut buf).ok()?;
+            Some(u64::from_ne_bytes(buf))
+        }
+    }
+
+    /// CPU time consumed by the calling thread.
+    pub fn thread_cpu_nanos() -> u64 {
+        let mut ts = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &raw mut ts) };
+        #[allow(clippy::cast_sign_loss)]
+        let nanos = ts.tv_sec as u64 * 1_000_000_000 + ts.tv_nsec as u64;
+        nanos
+    }
+}
@@ -1,11 +1,13 @@
-/// Module that updates the readme me with timing information.
+/// Module that updates the readme me with star and timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::answers::{Answers, Solved};
+use crate::template::timings::{MemoryStats, Timings, format_bytes};
+use crate::template::{Day, get_year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static STARS_MARKER: &str = "<!--- advent_readme_stars table --->";
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -30,8 +32,13 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+#[must_use]
+pub fn get_puzzle_url(day: Day, year: u16) -> String {
+    format!("https://adventofcode.com/{year}/day/{}", day.into_inner())
+}
+
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,55 +59,170 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+fn format_day_link(day: Day, year: Option<u16>) -> String {
+    match year {
+        Some(year) => format!(
+            "[Day {}]({}) ([puzzle]({}))",
+            day.into_inner(),
+            get_path_for_bin(day),
+            get_puzzle_url(day, year)
+        ),
+        None => format!("[Day {}]({})", day.into_inner(), get_path_for_bin(day)),
+    }
+}
+
+/// Builds the markdown table of timings, including a row with per-part subtotals. `day_label` renders the
+/// first cell of each day. If any timing has memory stats, the peak memory of each part is rendered as well.
+pub fn construct_timings_table(
+    timings: &Timings,
+    day_label: impl Fn(Day) -> String,
+) -> Vec<String> {
+    let part_1_millis = timings.part_millis(1);
+    let part_2_millis = timings.part_millis(2);
+    let has_memory = timings.has_memory();
+
+    let mut lines: Vec<String> = if has_memory {
+        vec![
+            "| Day | Part 1 | Part 2 | Memory (Part 1) | Memory (Part 2) |".into(),
+            "| :---: | :---: | :---:  | :---: | :---: |".into(),
+        ]
+    } else {
+        vec![
+            "| Day | Part 1 | Part 2 |".into(),
+            "| :---: | :---: | :---:  |".into(),
+        ]
+    };
+
+    let format_memory =
+        |m: Option<MemoryStats>| m.map_or_else(|| "-".into(), |m| format_bytes(m.peak_bytes));
+
+    for timing in &timings.data {
+        let mut line = format!(
+            "| {} | `{}` | `{}` |",
+            day_label(timing.day),
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-")
+        );
+
+        if has_memory {
+            line.push_str(&format!(
+                " `{}` | `{}` |",
+                format_memory(timing.part_1_memory),
+                format_memory(timing.part_2_memory)
+            ));
+        }
+
+        lines.push(line);
+    }
+
+    lines.push(format!(
+        "| **Total** | `{part_1_millis:.2}ms` | `{part_2_millis:.2}ms` |{}",
+        if has_memory { " | |" } else { "" }
+    ));
+
+    lines
+}
+
+fn construct_table(prefix: &str, timings: Timings, total_millis: f64, year: Option<u16>) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+    lines.extend(construct_timings_table(&timings, |day| {
+        format_day_link(day, year)
+    }));
+    lines.push(String::new());
+    lines.push(format!("**Total: {total_millis:.2}ms**"));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn construct_stars_table(prefix: &str, answers: &Answers, year: Option<u16>) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Results"),
+        None => format!("{prefix} Results"),
+    };
+
+    let star = |x: &Option<Solved>| if x.is_some() { "⭐" } else { " " };
+
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        STARS_MARKER.into(),
         header,
         String::new(),
         "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| :---: | :---: | :---: |".into(),
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+    for answer in answers.data.iter().filter(|a| a.stars() > 0) {
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            "| {} | {} | {} |",
+            format_day_link(answer.day, year),
+            star(&answer.part_1),
+            star(&answer.part_2)
         ));
     }
 
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(format!("**Total: {} ⭐**", answers.total_stars()));
+    lines.push(STARS_MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    year: Option<u16>,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", timings, total_millis, year);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Updates the stars table. Unlike the benchmarks table, the stars table is optional.
+/// Without any recorded answers, the table is left alone, e.g. so that it can still be maintained by the
+/// readme-stars Github action.
+fn update_stars_content(s: &mut String, answers: &Answers, year: Option<u16>) -> Result<(), Error> {
+    if !s.contains(STARS_MARKER) || answers.total_stars() == 0 {
+        return Ok(());
+    }
+
+    let positions = locate_table(s, STARS_MARKER)?;
+    let table = construct_stars_table("##", answers, year);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(timings: Timings, answers: &Answers) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let year = get_year();
+    update_stars_content(&mut readme, answers, year)?;
+    update_content(&mut readme, timings, total_millis, year)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
-#[cfg(feature = "test_lib")]
+/// Refresh only the stars table, e.g. after an answer was accepted.
+pub fn update_stars(answers: &Answers) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_stars_content(&mut readme, answers, get_year())?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(test)]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{MARKER, STARS_MARKER, update_content, update_stars_content};
+    use crate::{
+        day,
+        template::answers::Answers,
+        template::timings::{MemoryStats, Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -110,18 +232,21 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    ..Default::default()
                 },
             ],
         }
@@ -131,28 +256,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
+        assert!(s.contains("## Benchmarks"));
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +285,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, Some(2025)).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -169,9 +294,10 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) ([puzzle](https://adventofcode.com/2025/day/1)) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) ([puzzle](https://adventofcode.com/2025/day/2)) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) ([puzzle](https://adventofcode.com/2025/day/4)) | `40ms` | `50ms` |",
+            "| **Total** | `80.00ms` | `110.00ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +306,69 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_without_year() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_memory = Some(MemoryStats {
+            peak_bytes: 2048,
+            total_bytes: 4096,
+            total_blocks: 3,
+        });
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, None).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Memory (Part 1) | Memory (Part 2) |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `2.0 KiB` | `-` |"));
+    }
+
+    #[test]
+    fn skips_missing_stars_marker() {
+        let mut s = "# readme".to_string();
+        update_stars_content(&mut s, &Answers::default(), Some(2025)).unwrap();
+        assert_eq!(s, "# readme");
+    }
+
+    #[test]
+    fn keeps_stars_table_without_answers() {
+        let mut s = format!("{STARS_MARKER}\n## 2025 Results\n{STARS_MARKER}");
+        let original = s.clone();
+        update_stars_content(&mut s, &Answers::default(), Some(2025)).unwrap();
+        assert_eq!(s, original);
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut answers = Answers::default();
+        answers.insert(day!(1), 1, "42");
+        answers.insert(day!(1), 2, "43");
+        answers.insert(day!(3), 1, "44");
+
+        let mut s = format!("foo\n{}\nbaz", STARS_MARKER);
+        update_stars_content(&mut s, &answers, Some(2025)).unwrap();
+        update_stars_content(&mut s, &answers, Some(2025)).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2025 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) ([puzzle](https://adventofcode.com/2025/day/1)) | ⭐ | ⭐ |",
+            "| [Day 3](./src/bin/03.rs) ([puzzle](https://adventofcode.com/2025/day/3)) | ⭐ |   |",
+            "",
+            "**Total: 3 ⭐**",
+            "<!--- advent_readme_stars table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
 }
@@ -1,13 +1,18 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, io, path::Path};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, BenchConfig, BuildOptions, Day};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    timings::{Timing, Timings, duration_to_nanos},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    options: BuildOptions,
+    bench: Option<&BenchConfig>,
+    dir: &Path,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -24,7 +29,7 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output = child_commands::run_solution(day, bench, options, dir).unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
@@ -34,7 +39,7 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             }
         });
 
-    if is_timed {
+    if bench.is_some() {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -68,7 +73,10 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use crate::template::{
+        BenchConfig, BuildOptions, Day,
+        timings::{CpuStats, MemoryStats},
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,24 +84,31 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day in the project at `dir`.
+    pub fn run_solution(
+        day: Day,
+        bench: Option<&BenchConfig>,
+        options: BuildOptions,
+        dir: &Path,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !dir.join(get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
-
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        let mut args = vec![
+            "run".to_string(),
+            "--quiet".into(),
+            "--bin".into(),
+            day.to_string(),
+        ];
+        args.extend(options.cargo_args());
+
+        if let Some(bench) = bench {
+            // mirror `--time` flag and the benchmark configuration to child invocations.
+            args.push("--".into());
+            args.push("--time".into());
+            args.extend(bench.to_args());
         }
 
         // spawn child command with piped stdout/stderr.
@@ -101,6 +116,7 @@ pub mod child_commands {
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .current_dir(dir)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -131,9 +147,7 @@ pub mod child_commands {
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+            ..Default::default()
         };
 
         output
@@ -149,13 +163,18 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                let (memory, cpu) = parse_stats(l);
+                Some((part, timing_str, nanos, memory, cpu))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, memory, cpu)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_memory = memory;
+                    timings.part_1_cpu = cpu;
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_memory = memory;
+                    timings.part_2_cpu = cpu;
                 }
 
                 timings.total_nanos += nanos;
@@ -164,12 +183,7 @@ pub mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
@@ -179,18 +193,33 @@ pub mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = super::duration_to_nanos(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
 
+    /// Parse the stats printed in brackets after the timing, e.g. `[peak 1.0 KiB, 2.0 KiB in 3 allocations] [cpu 1.2µs]`.
+    fn parse_stats(line: &str) -> (Option<MemoryStats>, Option<CpuStats>) {
+        let mut memory = None;
+        let mut cpu = None;
+
+        let Some(stats) = line.split(" samples)").nth(1) else {
+            return (memory, cpu);
+        };
+
+        for group in stats.split('[').filter_map(|s| s.trim().strip_suffix(']')) {
+            if group.starts_with("cpu ") {
+                cpu = group.parse().ok();
+            } else {
+                memory = group.parse().ok();
+            }
+        }
+
+        (memory, cpu)
+    }
+
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
+    #[cfg(test)]
     macro_rules! assert_approx_eq {
         ($a:expr, $b:expr) => {{
             let (a, b) = (&$a, &$b);
@@ -203,7 +232,7 @@ pub mod child_commands {
         }};
     }
 
-    #[cfg(feature = "test_lib")]
+    #[cfg(test)]
     mod tests {
         use super::parse_exec_time;
 
@@ -239,6 +268,40 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_memory_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [peak 1.0 KiB, 2.0 KiB in 3 allocations]"
+                        .into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            assert_eq!(res.part_1_memory.unwrap().peak_bytes, 1024);
+            assert_eq!(res.part_1_memory.unwrap().total_blocks, 3);
+            assert_eq!(res.part_2_memory, None);
+        }
+
+        #[test]
+        fn parses_cpu_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [peak 1.0 KiB, 2.0 KiB in 3 allocations] [cpu 70.0ns, 420 instructions]"
+                        .into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples) [cpu 73.0ms]".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1_memory.unwrap().peak_bytes, 1024);
+            assert_eq!(res.part_1_cpu.unwrap().cpu_nanos, 70);
+            assert_eq!(res.part_1_cpu.unwrap().instructions, Some(420));
+            assert_eq!(res.part_2_memory, None);
+            assert_eq!(res.part_2_cpu.unwrap().cpu_nanos, 73_000_000);
+            assert_eq!(res.part_2_cpu.unwrap().cycles, None);
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
@@ -250,8 +313,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
         }
     }
 }
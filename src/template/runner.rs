@@ -1,76 +1,151 @@
-/// Encapsulates code that interacts with solution functions.
+//! Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{Write, stdout};
-use std::process::Output;
+use std::io::{Write, stdin, stdout};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::BenchConfig;
+use crate::template::answers::Answers;
+use crate::template::perf::{self, Counters};
+use crate::template::submissions::{ExampleStatus, Submission, Submissions};
+use crate::template::timings::{CpuStats, MemoryStats};
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli, readme_benchmarks};
 
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
+    let (result, duration, samples, memory, cpu) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let stats_str = format!(
+        "{}{}{}",
+        format_duration(&duration, samples),
+        memory.map(|m| format!(" [{m}]")).unwrap_or_default(),
+        cpu.map(|c| format!(" [{c}]")).unwrap_or_default()
+    );
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    print_result(&result, &part_str, &stats_str);
+
+    if let Some(result) = result
+        && let Some(submission) = submit_result(&result, day, part)
+    {
+        report_submission(submission, day, part, &result.to_string());
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///     The budget can be changed via [`BenchConfig`].
+///
+/// When the `dhat-heap` or `count-allocations` feature is enabled, the heap usage of the first execution is returned as well.
+/// When the `cpu-time` feature is enabled, the CPU time and hardware counters are returned, averaged over all samples.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<MemoryStats>, Option<CpuStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory, cpu) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        #[cfg(all(feature = "count-allocations", not(feature = "dhat-heap")))]
+        crate::template::alloc::reset();
+
+        let counters = Counters::start();
+        let result = func(input);
+        let cpu = counters.stop(1);
+
+        #[cfg(feature = "dhat-heap")]
+        let memory = {
+            let stats = dhat::HeapStats::get();
+            Some(MemoryStats {
+                peak_bytes: stats.max_bytes as u64,
+                total_bytes: stats.total_bytes,
+                total_blocks: stats.total_blocks,
+            })
+        };
+
+        #[cfg(all(feature = "count-allocations", not(feature = "dhat-heap")))]
+        let memory = {
+            let stats = crate::template::alloc::stats();
+            Some(MemoryStats {
+                peak_bytes: stats.peak_bytes,
+                total_bytes: stats.total_bytes,
+                total_blocks: stats.total_blocks,
+            })
+        };
+
+        #[cfg(not(any(feature = "dhat-heap", feature = "count-allocations")))]
+        let memory = None;
+
+        (result, memory, cpu)
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
     let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+        let config = BenchConfig::parse(&mut pico_args::Arguments::from_env())
+            .map_err(|e| format!("Invalid benchmark configuration: {e}"))
+            .and_then(|config| config.validate().map(|()| config));
+        let config = match config {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+        bench(func, input, &base_time, &config)
     } else {
-        (base_time, 1)
+        (base_time, 1, cpu)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, memory, run.2)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> (Duration, u128, Option<CpuStats>) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    if let Some(cpu) = config.pin_cpu
+        && let Err(e) = perf::pin_to_cpu(cpu)
+    {
+        eprintln!("\nCould not pin benchmark to cpu {cpu}: {e}");
+    }
+
+    let warm_up = Instant::now();
+    while warm_up.elapsed() < config.warm_up {
+        black_box(func(black_box(input)));
+    }
+
+    let bench_iterations = config.samples(base_time);
 
     let mut timers: Vec<Duration> = vec![];
 
+    let counters = Counters::start();
     for _ in 0..bench_iterations {
         let timer = Instant::now();
         black_box(func(black_box(input)));
         timers.push(timer.elapsed());
     }
+    let cpu = counters.stop(bench_iterations);
 
     (
         #[allow(clippy::cast_possible_truncation)]
         Duration::from_nanos(average_duration(&timers) as u64),
         bench_iterations,
+        cpu,
     )
 }
 
@@ -128,11 +203,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
+///  3. the submission was confirmed, either interactively or via `--yes`.
+///
+/// Before asking for confirmation, the answer is checked against the example tests and prior submissions.
+/// With `--dry-run`, the aoc-cli call is printed instead of run.
 fn submit_result<T: Display>(
-    result: T,
+    result: &T,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<aoc_cli::Verdict, aoc_cli::AocCommandError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,13 +234,129 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    let answer = result.to_string();
+    let examples: Option<ExampleStatus> = args
+        .iter()
+        .position(|x| x == "--examples")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok());
+
+    println!("Answer for day {day}, part {part}: {ANSI_BOLD}{answer}{ANSI_RESET}");
+    for warning in Submissions::read_from_file().warnings(
+        day,
+        part,
+        &answer,
+        examples,
+        &Answers::read_from_file(),
+    ) {
+        println!("⚠️  {warning}");
+    }
+
+    if args.contains(&"--dry-run".into()) {
+        println!(
+            "Dry run, not submitting. Would call: {} {}",
+            aoc_cli::get_executable(),
+            aoc_cli::submit_args(day, part, &answer).join(" ")
+        );
+        return None;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            "command \"{}\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.",
+            aoc_cli::get_executable()
         );
         process::exit(1);
     }
 
+    if !args.contains(&"--yes".into()) && !confirm("Submit this answer? [y/N] ") {
+        println!("Submission cancelled.");
+        process::exit(1);
+    }
+
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(day, part, &answer))
+}
+
+/// Ask a yes / no question on stdin, defaulting to no.
+fn confirm(question: &str) -> bool {
+    print!("{question}");
+    let _ = stdout().flush();
+
+    let mut line = String::new();
+    if stdin().read_line(&mut line).is_err() {
+        return false;
+    }
+
+    matches!(line.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Record the star of `part` in `answers` if `verdict` shows that it is solved. Returns whether `answers`
+/// changed.
+///
+/// Parts that were solved before, e.g. without the template, are reported as already solved. They only earn
+/// a star, as the submitted answer was not checked. Part 2 is also reported as already solved while it is
+/// locked, so it only earns a star that way once part 1 is solved.
+fn record_star(
+    answers: &mut Answers,
+    verdict: aoc_cli::Verdict,
+    day: Day,
+    part: u8,
+    answer: &str,
+) -> bool {
+    match verdict {
+        aoc_cli::Verdict::Correct => {
+            answers.insert(day, part, answer);
+            true
+        }
+        aoc_cli::Verdict::AlreadySolved
+            if !answers.is_solved(day, part) && (part == 1 || answers.is_solved(day, 1)) =>
+        {
+            answers.insert_star(day, part);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Print a summary of the verdict and exit with its exit code, unless the answer was correct.
+/// Solved parts are recorded in `data/answers.json`.
+fn report_submission(
+    submission: Result<aoc_cli::Verdict, aoc_cli::AocCommandError>,
+    day: Day,
+    part: u8,
+    answer: &str,
+) {
+    let verdict = match submission {
+        Ok(verdict) => verdict,
+        Err(e) => {
+            eprintln!("failed to call aoc-cli: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("{ANSI_BOLD}Day {day}, part {part}:{ANSI_RESET} {verdict}");
+
+    if let Err(e) = Submission::new(day, part, answer, verdict).append_to_file() {
+        eprintln!("failed to record submission: {e}");
+    }
+
+    let mut answers = Answers::read_from_file();
+    if record_star(&mut answers, verdict, day, part, answer) {
+        store_answers(&answers);
+    }
+
+    if verdict != aoc_cli::Verdict::Correct {
+        process::exit(verdict.exit_code());
+    }
+}
+
+/// Store `answers` in `data/answers.json` and refresh the stars table in the readme.
+fn store_answers(answers: &Answers) {
+    if let Err(e) = answers.store_file() {
+        eprintln!("failed to store answer: {e}");
+    }
+    if readme_benchmarks::update_stars(answers).is_err() {
+        eprintln!("failed to update the stars table in the readme.");
+    }
 }
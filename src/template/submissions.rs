@@ -0,0 +1,394 @@
+//! Append-only log of answers submitted via aoc-cli.
+//! Every submission is appended as a single JSON line, so that answers which are known to be wrong
+//! are not submitted twice.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+use crate::template::answers::Answers;
+use crate::template::aoc_cli::{Hint, Verdict};
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.jsonl";
+
+/// Result of the example tests of a part, run by `cargo solve` before submitting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExampleStatus {
+    Passed,
+    Failed,
+    /// No tests matched the part.
+    Missing,
+}
+
+impl Display for ExampleStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExampleStatus::Passed => write!(f, "passed"),
+            ExampleStatus::Failed => write!(f, "failed"),
+            ExampleStatus::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+impl FromStr for ExampleStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "passed" => Ok(ExampleStatus::Passed),
+            "failed" => Ok(ExampleStatus::Failed),
+            "missing" => Ok(ExampleStatus::Missing),
+            _ => Err(format!("unknown example status `{s}`")),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Submission {
+    /// Create a submission of `answer` that was just judged with `verdict`.
+    pub fn new(day: Day, part: u8, answer: &str, verdict: Verdict) -> Self {
+        Submission {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+        }
+    }
+
+    /// Append the submission to the submissions file.
+    pub fn append_to_file(&self) -> Result<(), Error> {
+        let line = JsonValue::from(self)
+            .stringify()
+            .map_err(|e| Error::other(e.to_string()))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(SUBMISSIONS_FILE_PATH)?;
+
+        writeln!(file, "{line}")
+    }
+}
+
+/// All submissions, in the order they were made.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub entries: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Read the submissions file. Lines that cannot be parsed are skipped with a warning.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(SUBMISSIONS_FILE_PATH)
+            .map(|s| Submissions::parse(&s))
+            .unwrap_or_default()
+    }
+
+    fn parse(s: &str) -> Self {
+        let entries = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .filter_map(|(i, line)| match Submission::try_from(line) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    eprintln!("Skipping line {} of submissions: {e}", i + 1);
+                    None
+                }
+            })
+            .collect();
+
+        Submissions { entries }
+    }
+
+    /// Reasons to double-check `answer` before submitting it for `part` of `day`.
+    pub fn warnings(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+        examples: Option<ExampleStatus>,
+        answers: &Answers,
+    ) -> Vec<String> {
+        let mut warnings = vec![];
+
+        match examples {
+            Some(ExampleStatus::Failed) => {
+                warnings.push(format!("The example tests of part {part} failed."));
+            }
+            Some(ExampleStatus::Missing) => {
+                warnings.push(format!("There are no example tests for part {part}."));
+            }
+            Some(ExampleStatus::Passed) | None => {}
+        }
+
+        if let Some(accepted) = answers.get(day, part) {
+            warnings.push(format!(
+                "Part {part} was already solved with the answer `{accepted}`."
+            ));
+        } else if answers.is_solved(day, part) {
+            warnings.push(format!("Part {part} was already solved."));
+        }
+
+        let previous: Vec<&Submission> = self
+            .entries
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .collect();
+
+        if let Some(submission) = previous
+            .iter()
+            .rev()
+            .find(|s| s.answer == answer && matches!(s.verdict, Verdict::Wrong(_)))
+        {
+            warnings.push(format!(
+                "This answer was submitted before: {}",
+                submission.verdict
+            ));
+        }
+
+        if let Ok(answer) = answer.parse::<i64>() {
+            let bound = |hint: Hint| {
+                previous
+                    .iter()
+                    .filter(move |s| s.verdict == Verdict::Wrong(Some(hint)))
+                    .filter_map(|s| s.answer.parse::<i64>().ok())
+            };
+
+            if let Some(high) = bound(Hint::TooHigh).min().filter(|x| answer >= *x) {
+                warnings.push(format!(
+                    "The answer is not lower than `{high}`, which was too high."
+                ));
+            }
+
+            if let Some(low) = bound(Hint::TooLow).max().filter(|x| answer <= *x) {
+                warnings.push(format!(
+                    "The answer is not higher than `{low}`, which was too low."
+                ));
+            }
+        }
+
+        warnings
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn verdict_to_str(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Correct => "correct",
+        Verdict::Wrong(Some(Hint::TooHigh)) => "too-high",
+        Verdict::Wrong(Some(Hint::TooLow)) => "too-low",
+        Verdict::Wrong(None) => "wrong",
+        Verdict::RateLimited(_) => "rate-limited",
+        Verdict::AlreadySolved => "already-solved",
+        Verdict::Unknown => "unknown",
+    }
+}
+
+fn verdict_from_str(s: &str) -> Option<Verdict> {
+    match s {
+        "correct" => Some(Verdict::Correct),
+        "too-high" => Some(Verdict::Wrong(Some(Hint::TooHigh))),
+        "too-low" => Some(Verdict::Wrong(Some(Hint::TooLow))),
+        "wrong" => Some(Verdict::Wrong(None)),
+        "rate-limited" => Some(Verdict::RateLimited(None)),
+        "already-solved" => Some(Verdict::AlreadySolved),
+        "unknown" => Some(Verdict::Unknown),
+        _ => None,
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(verdict_to_str(value.verdict).into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Submission {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let map = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected submission to be an object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = map
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let day = map
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = map
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = map
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = map
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| verdict_from_str(v))
+            .ok_or("Expected submission.verdict to be a known verdict.")?;
+
+        Ok(Submission {
+            timestamp,
+            day,
+            part,
+            answer,
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{ExampleStatus, Submission, Submissions};
+    use crate::{
+        day,
+        template::{
+            answers::Answers,
+            aoc_cli::{Hint, Verdict},
+        },
+    };
+    use tinyjson::JsonValue;
+
+    fn submission(answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            timestamp: 1_733_011_200,
+            day: day!(1),
+            part: 1,
+            answer: answer.into(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submission = submission("42", Verdict::Wrong(Some(Hint::TooLow)));
+        let line = JsonValue::from(&submission).stringify().unwrap();
+        assert_eq!(Submission::try_from(line.as_str()).unwrap(), submission);
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let submissions = Submissions::parse(
+            "{\"timestamp\":0,\"day\":\"01\",\"part\":1,\"answer\":\"3\",\"verdict\":\"correct\"}\nnot json\n",
+        );
+        assert_eq!(submissions.entries.len(), 1);
+    }
+
+    #[test]
+    fn warns_about_known_wrong_answers() {
+        let submissions = Submissions {
+            entries: vec![
+                submission("100", Verdict::Wrong(Some(Hint::TooHigh))),
+                submission("10", Verdict::Wrong(Some(Hint::TooLow))),
+            ],
+        };
+        let answers = Answers::default();
+
+        let warnings = submissions.warnings(day!(1), 1, "100", None, &answers);
+        assert_eq!(
+            warnings,
+            [
+                "This answer was submitted before: Wrong answer, too high.",
+                "The answer is not lower than `100`, which was too high."
+            ]
+        );
+
+        let warnings = submissions.warnings(day!(1), 1, "5", None, &answers);
+        assert_eq!(
+            warnings,
+            ["The answer is not higher than `10`, which was too low."]
+        );
+
+        assert!(
+            submissions
+                .warnings(day!(1), 1, "50", Some(ExampleStatus::Passed), &answers)
+                .is_empty()
+        );
+        assert!(
+            submissions
+                .warnings(day!(1), 2, "100", None, &answers)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn warns_about_failed_examples_and_solved_parts() {
+        let mut answers = Answers::default();
+        answers.insert(day!(1), 1, "42");
+
+        let warnings = Submissions::default().warnings(
+            day!(1),
+            1,
+            "43",
+            Some(ExampleStatus::Failed),
+            &answers,
+        );
+        assert_eq!(
+            warnings,
+            [
+                "The example tests of part 1 failed.",
+                "Part 1 was already solved with the answer `42`."
+            ]
+        );
+
+        answers.insert_star(day!(1), 2);
+        let warnings = Submissions::default().warnings(day!(1), 2, "43", None, &answers);
+        assert_eq!(warnings, ["Part 2 was already solved."]);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
 use crate::template::Day;
@@ -12,6 +12,44 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
+    pub part_1_cpu: Option<CpuStats>,
+    pub part_2_cpu: Option<CpuStats>,
+}
+
+impl Default for Timing {
+    /// A day without any timings.
+    fn default() -> Self {
+        Timing {
+            day: crate::day!(1),
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            part_1_memory: None,
+            part_2_memory: None,
+            part_1_cpu: None,
+            part_2_cpu: None,
+        }
+    }
+}
+
+/// Heap usage of a single solution part, as measured by dhat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    pub peak_bytes: u64,
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+}
+
+/// CPU time and hardware counters of a single solution part, averaged over all samples.
+/// Counters are `None` when perf events are not available on the machine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CpuStats {
+    pub cpu_nanos: u64,
+    pub instructions: Option<u64>,
+    pub cycles: Option<u64>,
+    pub cache_misses: Option<u64>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -38,12 +76,10 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Memory and CPU stats are replaced along with the times they were measured with, so a day measured
+    /// without them loses its old stats.
     pub fn merge(&self, new: &Self) -> Self {
-        let mut data: Vec<Timing> = vec![];
-
-        for timing in &new.data {
-            data.push(timing.clone());
-        }
+        let mut data: Vec<Timing> = new.data.clone();
 
         for timing in &self.data {
             if !data.iter().any(|t| t.day == timing.day) {
@@ -51,7 +87,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -60,6 +96,29 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Sum up the duration of one part across all timings as millis.
+    pub fn part_millis(&self, part: u8) -> f64 {
+        self.data
+            .iter()
+            .filter_map(|x| x.part_nanos(part))
+            .sum::<f64>()
+            / 1_000_000_f64
+    }
+
+    /// Whether any of the timings include memory stats.
+    pub fn has_memory(&self) -> bool {
+        self.data
+            .iter()
+            .any(|t| t.part_1_memory.is_some() || t.part_2_memory.is_some())
+    }
+
+    /// Whether any of the timings include CPU stats.
+    pub fn has_cpu(&self) -> bool {
+        self.data
+            .iter()
+            .any(|t| t.part_1_cpu.is_some() || t.part_2_cpu.is_some())
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -67,6 +126,159 @@ impl Timings {
     }
 }
 
+impl Timing {
+    /// Duration of `part` in nanoseconds, parsed from its formatted timing.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        match part {
+            1 => self.part_1.as_deref().and_then(duration_to_nanos),
+            2 => self.part_2.as_deref().and_then(duration_to_nanos),
+            _ => None,
+        }
+    }
+}
+
+/// Format a number of bytes with binary units, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024_f64;
+    let mut unit = 0;
+    while value >= 1024_f64 && unit < UNITS.len() - 1 {
+        value /= 1024_f64;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Parse a number of bytes formatted via [`format_bytes`].
+pub fn bytes_from_str(s: &str) -> Option<u64> {
+    let (value, unit) = s.trim().split_once(' ')?;
+    let value: f64 = value.parse().ok()?;
+    let exponent = match unit {
+        "B" => 0,
+        "KiB" => 1,
+        "MiB" => 2,
+        "GiB" => 3,
+        "TiB" => 4,
+        _ => return None,
+    };
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some((value * 1024_f64.powi(exponent)).round() as u64)
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, {} in {} allocations",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.total_bytes),
+            self.total_blocks
+        )
+    }
+}
+
+impl FromStr for MemoryStats {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("could not parse memory stats from `{s}`.");
+
+        let (peak, rest) = s
+            .trim()
+            .strip_prefix("peak ")
+            .and_then(|s| s.split_once(", "))
+            .ok_or_else(err)?;
+        let (total, blocks) = rest.split_once(" in ").ok_or_else(err)?;
+        let blocks = blocks.strip_suffix(" allocations").ok_or_else(err)?;
+
+        Ok(MemoryStats {
+            peak_bytes: bytes_from_str(peak).ok_or_else(err)?,
+            total_bytes: bytes_from_str(total).ok_or_else(err)?,
+            total_blocks: blocks.parse().map_err(|_| err())?,
+        })
+    }
+}
+
+impl Display for CpuStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "cpu {:.1?}",
+            std::time::Duration::from_nanos(self.cpu_nanos)
+        )?;
+
+        let counters = [
+            (self.instructions, "instructions"),
+            (self.cycles, "cycles"),
+            (self.cache_misses, "cache misses"),
+        ];
+
+        for (value, name) in counters {
+            if let Some(value) = value {
+                write!(f, ", {value} {name}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for CpuStats {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("could not parse cpu stats from `{s}`.");
+
+        let mut segments = s.trim().split(", ");
+
+        let cpu_time = segments
+            .next()
+            .and_then(|x| x.strip_prefix("cpu "))
+            .ok_or_else(err)?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let mut stats = CpuStats {
+            cpu_nanos: duration_to_nanos(cpu_time).ok_or_else(err)?.round() as u64,
+            instructions: None,
+            cycles: None,
+            cache_misses: None,
+        };
+
+        for segment in segments {
+            let (value, name) = segment.split_once(' ').ok_or_else(err)?;
+            let value = Some(value.parse().map_err(|_| err())?);
+            match name {
+                "instructions" => stats.instructions = value,
+                "cycles" => stats.cycles = value,
+                "cache misses" => stats.cache_misses = value,
+                _ => return Err(err()),
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+/// Parse a duration formatted via `{:?}`, e.g. `74.13ms`, to nanoseconds.
+pub fn duration_to_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse_to_float = |postfix: &str| s.split(postfix).next()?.trim().parse::<f64>().ok();
+
+    match s {
+        s if s.contains("ns") => parse_to_float("ns"),
+        s if s.contains("µs") => parse_to_float("µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float("ms").map(|x| x * 1_000_000_f64),
+        _ => parse_to_float("s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -87,7 +299,14 @@ impl TryFrom<String> for Timings {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        Timings::try_from(&json)
+    }
+}
 
+impl TryFrom<&JsonValue> for Timings {
+    type Error = String;
+
+    fn try_from(json: &JsonValue) -> Result<Self, Self::Error> {
         let json_data = json
             .get::<HashMap<String, JsonValue>>()
             .ok_or("expected JSON document to be an object.")?
@@ -133,6 +352,23 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        // memory stats are optional and only written when they were measured.
+        if let Some(memory) = &value.part_1_memory {
+            map.insert("part_1_memory".into(), JsonValue::from(memory));
+        }
+
+        if let Some(memory) = &value.part_2_memory {
+            map.insert("part_2_memory".into(), JsonValue::from(memory));
+        }
+
+        if let Some(cpu) = &value.part_1_cpu {
+            map.insert("part_1_cpu".into(), JsonValue::from(cpu));
+        }
+
+        if let Some(cpu) = &value.part_2_cpu {
+            map.insert("part_2_cpu".into(), JsonValue::from(cpu));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,18 +402,147 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let part_1_memory = json
+            .get("part_1_memory")
+            .filter(|v| !v.is_null())
+            .map(MemoryStats::try_from)
+            .transpose()?;
+
+        let part_2_memory = json
+            .get("part_2_memory")
+            .filter(|v| !v.is_null())
+            .map(MemoryStats::try_from)
+            .transpose()?;
+
+        let part_1_cpu = json
+            .get("part_1_cpu")
+            .filter(|v| !v.is_null())
+            .map(CpuStats::try_from)
+            .transpose()?;
+
+        let part_2_cpu = json
+            .get("part_2_cpu")
+            .filter(|v| !v.is_null())
+            .map(CpuStats::try_from)
+            .transpose()?;
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            part_1_memory,
+            part_2_memory,
+            part_1_cpu,
+            part_2_cpu,
         })
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+impl From<&MemoryStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "total_blocks".into(),
+            JsonValue::Number(value.total_blocks as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        let get_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            peak_bytes: get_number("peak_bytes")?,
+            total_bytes: get_number("total_bytes")?,
+            total_blocks: get_number("total_blocks")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&CpuStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &CpuStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "cpu_nanos".into(),
+            JsonValue::Number(value.cpu_nanos as f64),
+        );
+
+        let counters = [
+            ("instructions", value.instructions),
+            ("cycles", value.cycles),
+            ("cache_misses", value.cache_misses),
+        ];
+
+        for (key, counter) in counters {
+            map.insert(
+                key.into(),
+                counter.map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for CpuStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected cpu stats to be a JSON object.")?;
+
+        let get_counter = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as u64)
+        };
+
+        Ok(CpuStats {
+            cpu_nanos: get_counter("cpu_nanos").ok_or("Expected cpu.cpu_nanos to be a number.")?,
+            instructions: get_counter("instructions"),
+            cycles: get_counter("cycles"),
+            cache_misses: get_counter("cache_misses"),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
 mod tests {
     use crate::day;
 
@@ -191,18 +556,20 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
                     total_nanos: 4e+10,
+                    ..Default::default()
                 },
             ],
         }
@@ -245,6 +612,94 @@ mod tests {
         }
     }
 
+    mod memory {
+        use crate::template::timings::{MemoryStats, Timings, bytes_from_str, format_bytes};
+        use std::str::FromStr;
+
+        #[test]
+        fn formats_bytes() {
+            assert_eq!(format_bytes(512), "512 B");
+            assert_eq!(format_bytes(1536), "1.5 KiB");
+            assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+            assert_eq!(bytes_from_str("1.5 KiB"), Some(1536));
+            assert_eq!(bytes_from_str("1.5 kb"), None);
+        }
+
+        #[test]
+        fn roundtrips_memory_stats() {
+            let stats = MemoryStats {
+                peak_bytes: 100,
+                total_bytes: 1000,
+                total_blocks: 7,
+            };
+            assert_eq!(stats.to_string(), "peak 100 B, 1000 B in 7 allocations");
+            assert_eq!(MemoryStats::from_str(&stats.to_string()).unwrap(), stats);
+        }
+
+        #[test]
+        fn handles_json_memory_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_memory": { "peak_bytes": 10, "total_bytes": 20, "total_blocks": 2 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_memory.unwrap().total_bytes, 20);
+            assert_eq!(timing.part_2_memory, None);
+            assert!(timings.has_memory());
+        }
+    }
+
+    mod cpu {
+        use crate::template::timings::{CpuStats, Timings};
+        use std::str::FromStr;
+
+        #[test]
+        fn roundtrips_cpu_stats() {
+            let stats = CpuStats {
+                cpu_nanos: 1500,
+                instructions: Some(4200),
+                cycles: Some(3100),
+                cache_misses: Some(12),
+            };
+            assert_eq!(
+                stats.to_string(),
+                "cpu 1.5µs, 4200 instructions, 3100 cycles, 12 cache misses"
+            );
+            assert_eq!(CpuStats::from_str(&stats.to_string()).unwrap(), stats);
+        }
+
+        #[test]
+        fn roundtrips_cpu_stats_without_counters() {
+            let stats = CpuStats {
+                cpu_nanos: 2_000_000,
+                instructions: None,
+                cycles: None,
+                cache_misses: None,
+            };
+            assert_eq!(stats.to_string(), "cpu 2.0ms");
+            assert_eq!(CpuStats::from_str(&stats.to_string()).unwrap(), stats);
+            assert!(CpuStats::from_str("peak 1 B, 1 B in 1 allocations").is_err());
+        }
+
+        #[test]
+        fn handles_json_cpu_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_2_cpu": { "cpu_nanos": 900000, "instructions": 100, "cycles": null, "cache_misses": null } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_cpu, None);
+            assert_eq!(timing.part_2_cpu.unwrap().cpu_nanos, 900_000);
+            assert_eq!(timing.part_2_cpu.unwrap().instructions, Some(100));
+            assert_eq!(timing.part_2_cpu.unwrap().cycles, None);
+            assert!(timings.has_cpu());
+
+            let roundtrip = Timings::try_from(
+                tinyjson::JsonValue::from(timings.clone())
+                    .stringify()
+                    .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(roundtrip.data[0].part_2_cpu, timing.part_2_cpu);
+        }
+    }
+
     mod serialization {
         use super::get_mock_timings;
         use std::collections::HashMap;
@@ -253,7 +708,7 @@ mod tests {
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
-            let value = JsonValue::try_from(timings).unwrap();
+            let value = JsonValue::from(timings);
             assert_eq!(
                 value
                     .get::<HashMap<String, JsonValue>>()
@@ -282,10 +737,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(day!(1)), true);
+            assert!(timings.is_day_complete(day!(1)));
         }
 
         #[test]
@@ -294,12 +750,12 @@ mod tests {
                 data: vec![Timing {
                     day: day!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
 
         #[test]
@@ -307,20 +763,18 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{CpuStats, MemoryStats, Timing, Timings},
         };
 
         use super::get_mock_timings;
@@ -331,9 +785,7 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -351,9 +803,7 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -365,6 +815,42 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn replaces_stats_with_their_timing() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1: Some("10ms".into()),
+                    total_nanos: 10_000_000_f64,
+                    part_1_memory: Some(MemoryStats {
+                        peak_bytes: 1,
+                        total_bytes: 2,
+                        total_blocks: 3,
+                    }),
+                    part_1_cpu: Some(CpuStats {
+                        cpu_nanos: 10_000_000,
+                        instructions: None,
+                        cycles: None,
+                        cache_misses: None,
+                    }),
+                    ..Default::default()
+                }],
+            };
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1: Some("1ms".into()),
+                    total_nanos: 1_000_000_f64,
+                    ..Default::default()
+                }],
+            };
+
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data[0].part_1.as_deref(), Some("1ms"));
+            assert_eq!(merged.data[0].part_1_memory, None);
+            assert_eq!(merged.data[0].part_1_cpu, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -0,0 +1,342 @@
+//! Re-runs a day whenever one of its source or data files changes.
+//! Change detection works by polling modification times, so no platform-specific file watcher is needed.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// The answer and timing a single part printed during a run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: String,
+    pub answer: String,
+    pub duration: Option<String>,
+}
+
+/// What we remember about a run in order to compare it with the next one.
+#[derive(Clone, Debug, Default)]
+struct RunSummary {
+    parts: Vec<PartResult>,
+    test_result: Option<String>,
+}
+
+/// Watch the files relevant to `day` and re-run `cargo <cmd_args>` on every change.
+/// When `is_test` is set, `cmd_args` is expected to run the tests of the day instead of solving it.
+pub fn watch(day: Day, cmd_args: &[String], is_test: bool) -> ! {
+    let mut previous: Option<RunSummary> = None;
+    let mut snapshot = take_snapshot(day);
+
+    loop {
+        print!("{ANSI_CLEAR_SCREEN}");
+        println!(
+            "{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}(watching for changes){ANSI_RESET}"
+        );
+        println!("------");
+
+        let current = match run(cmd_args) {
+            Ok(output) => summarize(&output),
+            Err(e) => {
+                eprintln!("Failed to run cargo: {e}");
+                RunSummary::default()
+            }
+        };
+
+        if let Some(previous) = &previous {
+            print_comparison(previous, &current, is_test);
+        }
+
+        previous = Some(current);
+        snapshot = wait_for_change(day, snapshot);
+    }
+}
+
+/// Collect the files which trigger a re-run when modified.
+fn watched_paths(day: Day) -> Vec<PathBuf> {
+    let mut paths = vec![
+        PathBuf::from(format!("src/bin/{day}.rs")),
+        PathBuf::from("src/lib.rs"),
+    ];
+
+    collect_files(Path::new("src/template"), &mut paths);
+
+    for folder in ["inputs", "examples"] {
+        let Ok(entries) = fs::read_dir(Path::new("data").join(folder)) else {
+            continue;
+        };
+
+        // matches both `01.txt` and part-specific example files like `01-2.txt`.
+        paths.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| is_day_data_file(name, day))
+                }),
+        );
+    }
+
+    paths
+}
+
+fn is_day_data_file(file_name: &str, day: Day) -> bool {
+    let Some(stem) = file_name.strip_suffix(".txt") else {
+        return false;
+    };
+
+    let day = day.to_string();
+    stem == day
+        || stem
+            .strip_prefix(&day)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .is_some_and(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn collect_files(dir: &Path, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_files(&path, paths);
+        } else {
+            paths.push(path);
+        }
+    }
+}
+
+fn take_snapshot(day: Day) -> HashMap<PathBuf, Option<SystemTime>> {
+    watched_paths(day)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Block until the set of watched files or one of their modification times changes.
+fn wait_for_change(
+    day: Day,
+    snapshot: HashMap<PathBuf, Option<SystemTime>>,
+) -> HashMap<PathBuf, Option<SystemTime>> {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = take_snapshot(day);
+        if current != snapshot {
+            // editors often write files in several steps, give them a moment to settle.
+            thread::sleep(POLL_INTERVAL);
+            return take_snapshot(day);
+        }
+    }
+}
+
+/// Run cargo, forwarding its output while grabbing stdout lines.
+fn run(cmd_args: &[String]) -> Result<Vec<String>, io::Error> {
+    let mut cmd = Command::new("cargo")
+        .args(cmd_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let broken_pipe = || io::Error::from(io::ErrorKind::BrokenPipe);
+    let stdout = BufReader::new(cmd.stdout.take().ok_or_else(broken_pipe)?);
+    let stderr = BufReader::new(cmd.stderr.take().ok_or_else(broken_pipe)?);
+
+    let thread = thread::spawn(move || {
+        stderr.lines().map_while(Result::ok).for_each(|line| {
+            eprintln!("{line}");
+        });
+    });
+
+    let mut output = vec![];
+
+    for line in stdout.lines() {
+        let line = line?;
+        println!("{line}");
+        output.push(line);
+    }
+
+    let _ = thread.join();
+    cmd.wait()?;
+
+    Ok(output)
+}
+
+fn summarize(output: &[String]) -> RunSummary {
+    RunSummary {
+        parts: output.iter().filter_map(|l| parse_part_line(l)).collect(),
+        test_result: output
+            .iter()
+            .rev()
+            .map(|l| strip_ansi(l))
+            .find(|l| l.starts_with("test result:")),
+    }
+}
+
+/// Parse a line like `Part 1: 42 (1.2µs)` as printed by `runner::run_part`.
+pub fn parse_part_line(line: &str) -> Option<PartResult> {
+    let line = strip_ansi(line);
+    // intermediate results are overwritten with a carriage return, only keep the final one.
+    let line = line.rsplit('\r').next()?.trim_end();
+
+    let (part, rest) = line.split_once(": ")?;
+    if !part.starts_with("Part ") {
+        return None;
+    }
+
+    // memory and cpu stats are printed in brackets after the timing when profiling.
+    let mut rest = rest;
+    while let Some((head, stats)) = rest.rsplit_once(" [")
+        && stats.ends_with(']')
+    {
+        rest = head;
+    }
+
+    let (answer, duration) = match rest.rsplit_once(" (") {
+        Some((answer, duration)) if duration.ends_with(')') => {
+            (answer, Some(duration.trim_end_matches(')').to_string()))
+        }
+        _ => (rest, None),
+    };
+
+    Some(PartResult {
+        part: part.to_string(),
+        answer: answer.trim().to_string(),
+        duration,
+    })
+}
+
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip the escape sequence up to and including its final letter.
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+
+    out
+}
+
+fn print_comparison(previous: &RunSummary, current: &RunSummary, is_test: bool) {
+    println!();
+    println!("{ANSI_BOLD}Previous run → this run{ANSI_RESET}");
+
+    if is_test {
+        let fmt = |r: &Option<String>| r.clone().unwrap_or_else(|| "-".into());
+        println!("Before: {}", fmt(&previous.test_result));
+        println!("After:  {}", fmt(&current.test_result));
+        return;
+    }
+
+    for part in &current.parts {
+        match previous.parts.iter().find(|p| p.part == part.part) {
+            Some(prev) => println!("{}", format_change(prev, part)),
+            None => println!("{}: {} (new)", part.part, part.answer),
+        }
+    }
+}
+
+fn format_change(previous: &PartResult, current: &PartResult) -> String {
+    let duration = |p: &PartResult| p.duration.clone().unwrap_or_else(|| "-".into());
+
+    let answer = if previous.answer == current.answer {
+        format!("{} (unchanged)", current.answer)
+    } else {
+        format!(
+            "{} → {ANSI_BOLD}{}{ANSI_RESET}",
+            previous.answer, current.answer
+        )
+    };
+
+    format!(
+        "{}: {answer} [{} → {}]",
+        current.part,
+        duration(previous),
+        duration(current)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PartResult, is_day_data_file, parse_part_line};
+    use crate::day;
+
+    #[test]
+    fn parses_final_part_lines() {
+        let res = parse_part_line("Part 1: 42\rPart 1: \x1b[1m42\x1b[0m (1.2µs)");
+        assert_eq!(
+            res,
+            Some(PartResult {
+                part: "Part 1".into(),
+                answer: "42".into(),
+                duration: Some("1.2µs".into()),
+            })
+        );
+    }
+
+    #[test]
+    fn parses_timed_part_lines() {
+        let res = parse_part_line("Part 2: \x1b[1m10\x1b[0m (74.13ms @ 99 samples)").unwrap();
+        assert_eq!(res.answer, "10");
+        assert_eq!(res.duration, Some("74.13ms @ 99 samples".into()));
+    }
+
+    #[test]
+    fn parses_part_lines_with_memory() {
+        let res =
+            parse_part_line("Part 1: 42 (1.2µs) [peak 1.0 KiB, 2.0 KiB in 3 allocations]").unwrap();
+        assert_eq!(res.answer, "42");
+        assert_eq!(res.duration, Some("1.2µs".into()));
+
+        let res = parse_part_line(
+            "Part 1: 42 (1.2µs) [peak 1.0 KiB, 2.0 KiB in 3 allocations] [cpu 1.1µs, 420 cycles]",
+        )
+        .unwrap();
+        assert_eq!(res.answer, "42");
+        assert_eq!(res.duration, Some("1.2µs".into()));
+    }
+
+    #[test]
+    fn parses_unsolved_parts() {
+        let res = parse_part_line("Part 2: ✖             ").unwrap();
+        assert_eq!(res.answer, "✖");
+        assert_eq!(res.duration, None);
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        assert_eq!(parse_part_line("   Compiling advent_of_code v0.12.0"), None);
+        assert_eq!(parse_part_line("test result: ok. 2 passed"), None);
+    }
+
+    #[test]
+    fn matches_day_data_files() {
+        assert!(is_day_data_file("01.txt", day!(1)));
+        assert!(is_day_data_file("01-2.txt", day!(1)));
+        assert!(!is_day_data_file("11.txt", day!(1)));
+        assert!(!is_day_data_file("01-.txt", day!(1)));
+        assert!(!is_day_data_file("01.md", day!(1)));
+    }
+}
@@ -0,0 +1,236 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+use common::{TestDir, fixture, stderr, stdout};
+
+/// Input of the fixture solution, with the answers `3` and `6`.
+const INPUT: &str = "1\n2\n3\n";
+
+/// The template's command-line, using the fake aoc-cli.
+fn template(dir: &TestDir, args: &[&str]) -> Command {
+    dir.aoc_command_for(env!("CARGO_BIN_EXE_advent_of_code"), args)
+}
+
+fn run_template(dir: &TestDir, args: &[&str]) -> Output {
+    template(dir, args).output().unwrap()
+}
+
+/// The fixture solution of day 1, using the fake aoc-cli.
+fn solution(dir: &TestDir, args: &[&str]) -> Command {
+    dir.aoc_command_for(&fixture().solution, args)
+}
+
+fn test_dir(name: &str) -> TestDir {
+    let dir = TestDir::new(name);
+    dir.fake_aoc_day("07", "1 2 3\n", "# Day 7\n", ["6", "12"]);
+    dir
+}
+
+#[test]
+fn downloads_input_and_puzzle() {
+    let dir = test_dir("aoc-download");
+
+    let output = run_template(&dir, &["download", "7"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Successfully wrote input to \"data/inputs/07.txt\""));
+
+    assert_eq!(dir.read("data/inputs/07.txt"), "1 2 3\n");
+    assert_eq!(dir.read("data/puzzles/07.md"), "# Day 7\n");
+    assert_eq!(
+        dir.aoc_invocations(),
+        [
+            "--overwrite --input-file data/inputs/07.txt --puzzle-file data/puzzles/07.md --year 2025 --day 07 download"
+        ]
+    );
+}
+
+#[test]
+fn omits_year_if_not_configured() {
+    let dir = test_dir("aoc-no-year");
+
+    let output = template(&dir, &["download", "7"])
+        .env_remove("AOC_YEAR")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    assert_eq!(
+        dir.aoc_invocations(),
+        [
+            "--overwrite --input-file data/inputs/07.txt --puzzle-file data/puzzles/07.md --day 07 download"
+        ]
+    );
+}
+
+#[test]
+fn reports_missing_aoc_cli() {
+    let dir = TestDir::new("aoc-missing");
+
+    let output = dir
+        .command(&["download", "7"])
+        .env("AOC_CLI", dir.path.join("bin").join("aoc"))
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("cargo install aoc-cli"));
+}
+
+#[test]
+fn scaffolds_downloads_solves_and_submits() {
+    let dir = TestDir::new("aoc-workflow");
+    dir.fake_aoc_day("01", INPUT, "# Day 1\n", ["3", "6"]);
+
+    let output = run_template(&dir, &["scaffold", "1", "--download"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(dir.exists("src/bin/01.rs"));
+    assert_eq!(dir.read("data/inputs/01.txt"), INPUT);
+
+    // the fixture solution stands in for the scaffolded one.
+    let output = solution(&dir, &["--submit", "1", "--yes"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("That's the right answer!"));
+    assert!(stdout(&output).contains("Day 01, part 1:\u{1b}[0m Correct answer!"));
+
+    assert_eq!(
+        dir.aoc_invocations().last().unwrap(),
+        "--year 2025 --day 01 submit 1 3"
+    );
+    assert!(dir.read("data/answers.json").contains(r#""part_1": "3""#));
+}
+
+#[test]
+fn reports_wrong_answers() {
+    let dir = TestDir::new("aoc-wrong-answer");
+    dir.fake_aoc_day("01", INPUT, "# Day 1\n", ["3", "5"]);
+    dir.write("data/inputs/01.txt", INPUT);
+
+    let output = solution(&dir, &["--submit", "2", "--yes"])
+        .output()
+        .unwrap();
+    assert!(stdout(&output).contains("That's not the right answer; your answer is too high."));
+    assert!(stdout(&output).contains("Wrong answer, too high."));
+    assert_eq!(output.status.code(), Some(2));
+    assert!(!dir.exists("data/answers.json"));
+}
+
+#[test]
+fn reports_already_solved_parts() {
+    let dir = TestDir::new("aoc-already-solved");
+    dir.fake_aoc_day("01", INPUT, "# Day 1\n", ["3", "6"]);
+    dir.write("data/inputs/01.txt", INPUT);
+
+    let submit = || {
+        solution(&dir, &["--submit", "1", "--yes"])
+            .output()
+            .unwrap()
+    };
+
+    assert!(submit().status.success());
+
+    let output = submit();
+    assert!(stdout(&output).contains("Already solved"));
+    assert_eq!(output.status.code(), Some(4));
+}
+
+#[test]
+fn records_parts_solved_before() {
+    let dir = TestDir::new("aoc-solved-before");
+    dir.fake_aoc_day("01", INPUT, "# Day 1\n", ["3", "6"]);
+    dir.write("fake-aoc/answers/01-1.solved", "3");
+    dir.write("fake-aoc/answers/01-2.solved", "6");
+    dir.write("data/inputs/01.txt", INPUT);
+
+    let submit = |part: &str| {
+        solution(&dir, &["--submit", part, "--yes"])
+            .output()
+            .unwrap()
+    };
+
+    // a locked part 2 is reported as already solved, too.
+    assert_eq!(submit("2").status.code(), Some(4));
+    assert!(!dir.exists("data/answers.json"));
+
+    assert_eq!(submit("1").status.code(), Some(4));
+    assert_eq!(submit("2").status.code(), Some(4));
+    // the submitted answers were not checked, so only the stars are recorded.
+    let answers = dir.read("data/answers.json");
+    assert!(answers.contains(r#""part_1": true"#));
+    assert!(answers.contains(r#""part_2": true"#));
+    assert!(!answers.contains(r#""3""#));
+}
+
+#[test]
+fn prints_dry_runs_without_submitting() {
+    let dir = TestDir::new("aoc-dry-run");
+    dir.fake_aoc_day("01", INPUT, "# Day 1\n", ["3", "6"]);
+    dir.write("data/inputs/01.txt", INPUT);
+
+    let output = solution(&dir, &["--submit", "1", "--dry-run"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Would call: "));
+    assert!(stdout(&output).contains("fake-aoc --year 2025 --day 01 submit 1 3"));
+    assert!(dir.aoc_invocations().iter().all(|x| !x.contains("submit")));
+}
+
+#[test]
+fn asks_for_confirmation() {
+    let dir = TestDir::new("aoc-confirm");
+    dir.fake_aoc_day("01", INPUT, "# Day 1\n", ["3", "6"]);
+    dir.write("data/inputs/01.txt", INPUT);
+
+    let submit = |reply: &str| {
+        let mut child = solution(&dir, &["--submit", "1"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(reply.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    };
+
+    let output = submit("n\n");
+    assert!(stdout(&output).contains("Submission cancelled."));
+    assert_eq!(output.status.code(), Some(1));
+    assert!(dir.aoc_invocations().iter().all(|x| !x.contains("submit")));
+
+    let output = submit("y\n");
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("Correct answer!"));
+}
+
+#[test]
+fn warns_about_previously_wrong_answers() {
+    let dir = TestDir::new("aoc-previous");
+    dir.fake_aoc_day("01", INPUT, "# Day 1\n", ["3", "5"]);
+    dir.write("data/inputs/01.txt", INPUT);
+
+    let submit = |args: &[&str]| solution(&dir, args).output().unwrap();
+
+    assert_eq!(submit(&["--submit", "2", "--yes"]).status.code(), Some(2));
+    assert!(
+        dir.read("data/submissions.jsonl")
+            .contains(r#""verdict":"too-high""#)
+    );
+
+    let output = submit(&["--submit", "2", "--dry-run", "--examples", "failed"]);
+    let stdout = stdout(&output);
+    assert!(stdout.contains("The example tests of part 2 failed."));
+    assert!(stdout.contains("This answer was submitted before: Wrong answer, too high."));
+    assert!(stdout.contains("The answer is not lower than `6`, which was too high."));
+}
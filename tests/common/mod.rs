@@ -0,0 +1,229 @@
+//! Helpers for integration tests that run the template's command-line in a temporary project directory.
+#![allow(dead_code)]
+
+use std::{
+    env,
+    ffi::OsStr,
+    fs,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    sync::OnceLock,
+};
+
+/// Environment variables that configure the benchmark budget of `cargo time`.
+const BENCH_VARS: [&str; 5] = [
+    "AOC_BENCH_TARGET",
+    "AOC_BENCH_MIN_SAMPLES",
+    "AOC_BENCH_MAX_SAMPLES",
+    "AOC_BENCH_WARM_UP",
+    "AOC_BENCH_PIN_CPU",
+];
+
+/// A solution of day 1 for the fixture package: part one counts the lines, part two sums them.
+const FIXTURE_SOLUTION: &str = r#"advent_of_code::solution!(1);
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(input.lines().count() as u64)
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    input.lines().map(|line| line.trim().parse::<u64>().ok()).sum()
+}
+"#;
+
+/// Executables of a small package built by the tests, so that they don't depend on the solutions in `src/bin`.
+pub struct Fixture {
+    /// The fake aoc-cli of `tests/support/fake_aoc.rs`.
+    pub fake_aoc: PathBuf,
+    /// A solution of day 1, see [`FIXTURE_SOLUTION`].
+    pub solution: PathBuf,
+}
+
+fn fixture_manifest() -> String {
+    let root = env!("CARGO_MANIFEST_DIR");
+    let fake_aoc = Path::new(root).join("tests/support/fake_aoc.rs");
+    format!(
+        r#"[package]
+name = "fixture"
+version = "0.0.0"
+edition = "2024"
+publish = false
+
+[workspace]
+
+[[bin]]
+name = "01"
+path = "src/bin/01.rs"
+
+[[bin]]
+name = "fake-aoc"
+path = {fake_aoc:?}
+
+[dependencies]
+advent_of_code = {{ path = {root:?} }}
+pico-args = "0.5.0"
+"#
+    )
+}
+
+/// Build the fixture package once per test binary, in the tests' temporary target directory.
+pub fn fixture() -> &'static Fixture {
+    static FIXTURE: OnceLock<Fixture> = OnceLock::new();
+
+    FIXTURE.get_or_init(|| {
+        let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("fixture");
+        fs::create_dir_all(dir.join("src/bin")).unwrap();
+        fs::write(dir.join("Cargo.toml"), fixture_manifest()).unwrap();
+        fs::write(dir.join("src/bin/01.rs"), FIXTURE_SOLUTION).unwrap();
+        // use the same dependency versions as this package.
+        let _ = fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.lock"),
+            dir.join("Cargo.lock"),
+        );
+
+        let target = dir.join("target");
+        let status = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+            .args(["build", "--quiet"])
+            .current_dir(&dir)
+            .env("CARGO_TARGET_DIR", &target)
+            .status()
+            .unwrap();
+        assert!(status.success(), "could not build the test fixture");
+
+        Fixture {
+            fake_aoc: target.join("debug").join("fake-aoc"),
+            solution: target.join("debug").join("01"),
+        }
+    })
+}
+
+/// A temporary project directory with the template's folder layout, removed again when dropped.
+pub struct TestDir {
+    pub path: PathBuf,
+}
+
+impl TestDir {
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("aoc-test-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+
+        for dir in [
+            "src/bin",
+            "data/inputs",
+            "data/examples",
+            "data/puzzles",
+            "bin",
+            "fake-aoc/inputs",
+            "fake-aoc/puzzles",
+            "fake-aoc/answers",
+        ] {
+            fs::create_dir_all(path.join(dir)).unwrap();
+        }
+
+        TestDir { path }
+    }
+
+    pub fn write(&self, file: &str, contents: &str) {
+        fs::write(self.path.join(file), contents).unwrap();
+    }
+
+    pub fn read(&self, file: &str) -> String {
+        fs::read_to_string(self.path.join(file)).unwrap_or_default()
+    }
+
+    pub fn exists(&self, file: &str) -> bool {
+        self.path.join(file).exists()
+    }
+
+    /// Install a fake executable named `name` that runs the shell `script`.
+    pub fn fake_executable(&self, name: &str, script: &str) {
+        let path = self.path.join("bin").join(name);
+        fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    /// Lines written by a fake executable that records its arguments via `record_args`.
+    pub fn recorded_args(&self, name: &str) -> Vec<String> {
+        self.read(&format!("{name}_args.txt"))
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    /// Serve `input`, `puzzle` and the correct answers of `day` from the fake aoc-cli.
+    pub fn fake_aoc_day(&self, day: &str, input: &str, puzzle: &str, answers: [&str; 2]) {
+        self.write(&format!("fake-aoc/inputs/{day}.txt"), input);
+        self.write(&format!("fake-aoc/puzzles/{day}.md"), puzzle);
+        for (part, answer) in answers.iter().enumerate() {
+            self.write(&format!("fake-aoc/answers/{day}-{}.txt", part + 1), answer);
+        }
+    }
+
+    /// Invocations of the fake aoc-cli, one line of arguments each.
+    pub fn aoc_invocations(&self) -> Vec<String> {
+        self.read("fake-aoc/invocations.log")
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    /// The template's command-line, run inside this directory with fake executables on the `PATH`.
+    pub fn command(&self, args: &[&str]) -> Command {
+        self.command_for(env!("CARGO_BIN_EXE_advent_of_code"), args)
+    }
+
+    /// Run `program` inside this directory with fake executables on the `PATH`.
+    pub fn command_for(&self, program: impl AsRef<OsStr>, args: &[&str]) -> Command {
+        let bin_dir = self.path.join("bin");
+        let path = env::var_os("PATH").unwrap_or_default();
+        let paths = std::iter::once(bin_dir).chain(env::split_paths(&path));
+
+        let mut cmd = Command::new(program);
+        cmd.args(args)
+            .current_dir(&self.path)
+            .env("PATH", env::join_paths(paths).unwrap())
+            .env("AOC_YEAR", "2025");
+
+        // keep the benchmark budget at its defaults, whatever is configured in `.cargo/config.toml`.
+        for var in BENCH_VARS {
+            cmd.env_remove(var);
+        }
+        cmd
+    }
+
+    /// Like [`TestDir::command_for`], with the fixture's fake aoc-cli serving the data in `fake-aoc`.
+    pub fn aoc_command_for(&self, program: impl AsRef<OsStr>, args: &[&str]) -> Command {
+        let mut cmd = self.command_for(program, args);
+        cmd.env("AOC_CLI", &fixture().fake_aoc)
+            .env("FAKE_AOC_DIR", self.path.join("fake-aoc"));
+        cmd
+    }
+
+    pub fn run(&self, args: &[&str]) -> Output {
+        self.command(args).output().unwrap()
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Shell snippet that appends the arguments of a fake executable as one line to `<name>_args.txt`.
+pub fn record_args(name: &str) -> String {
+    format!("echo \"$@\" >> {name}_args.txt")
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
+
+pub fn path_str(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
@@ -0,0 +1,97 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{TestDir, fixture, record_args, stderr, stdout};
+
+/// Fake `cargo` that records how solutions are invoked and prints timed results.
+fn fake_cargo(dir: &TestDir) {
+    dir.fake_executable(
+        "cargo",
+        &format!(
+            "{}\necho \"Part 1: 42 (1.0ms @ 10 samples)\"\necho \"Part 2: 43 (2.0ms @ 10 samples)\"",
+            record_args("cargo")
+        ),
+    );
+}
+
+#[test]
+fn runs_scaffolded_days() {
+    let dir = TestDir::new("run-multi-all");
+    fake_cargo(&dir);
+    dir.write("src/bin/01.rs", "");
+    dir.write("src/bin/03.rs", "");
+
+    let output = dir.run(&["all", "--release"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    assert_eq!(
+        dir.recorded_args("cargo"),
+        [
+            "run --quiet --bin 01 --release",
+            "run --quiet --bin 03 --release"
+        ]
+    );
+
+    let stdout = stdout(&output);
+    assert!(stdout.contains("Day 01"));
+    assert!(stdout.contains("Part 1: 42"));
+    assert!(stdout.contains("Not solved."));
+}
+
+#[test]
+fn passes_benchmark_settings_to_solutions() {
+    let dir = TestDir::new("run-multi-time");
+    fake_cargo(&dir);
+    dir.write("src/bin/01.rs", "");
+
+    let output = dir.run(&["time", "1", "--target", "10ms", "--min-samples", "2"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    assert_eq!(
+        dir.recorded_args("cargo"),
+        [
+            "run --quiet --bin 01 --release -- --time --target 10ms --min-samples 2 --max-samples 10000 --warm-up 0ns"
+        ]
+    );
+    assert!(stdout(&output).contains("3.00ms"));
+}
+
+#[test]
+fn rejects_invalid_benchmark_settings_in_solutions() {
+    let dir = TestDir::new("run-multi-invalid-settings");
+    dir.write("data/inputs/01.txt", "1\n2\n3\n");
+
+    let output = dir
+        .command_for(
+            &fixture().solution,
+            &["--time", "--min-samples", "10", "--max-samples", "5"],
+        )
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("`--min-samples` cannot be larger than `--max-samples`."));
+}
+
+#[test]
+fn stores_parsed_timings() {
+    let dir = TestDir::new("run-multi-store");
+    fake_cargo(&dir);
+    dir.write("src/bin/01.rs", "");
+    dir.write(
+        "README.md",
+        "<!--- benchmarking table ---><!--- benchmarking table --->",
+    );
+
+    let output = dir.run(&["time", "1", "--store"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let timings = dir.read("data/timings.json");
+    assert!(timings.contains("\"part_1\": \"1.0ms\""));
+    assert!(timings.contains("\"part_2\": \"2.0ms\""));
+    assert!(
+        dir.read("README.md")
+            .contains("| **Total** | `1.00ms` | `2.00ms` |")
+    );
+    assert_eq!(dir.read("data/timings_history.jsonl").lines().count(), 1);
+}
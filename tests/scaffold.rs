@@ -0,0 +1,60 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{TestDir, stderr};
+
+#[test]
+fn scaffolds_module_and_data_files() {
+    let dir = TestDir::new("scaffold");
+
+    let output = dir.run(&["scaffold", "7"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    assert!(
+        dir.read("src/bin/07.rs")
+            .starts_with("advent_of_code::solution!(7);")
+    );
+    assert!(dir.exists("data/inputs/07.txt"));
+    assert!(dir.exists("data/examples/07.txt"));
+}
+
+#[test]
+fn does_not_overwrite_existing_modules() {
+    let dir = TestDir::new("scaffold-overwrite");
+    dir.write("src/bin/07.rs", "// my solution");
+
+    let output = dir.run(&["scaffold", "7"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Failed to create module file"));
+    assert_eq!(dir.read("src/bin/07.rs"), "// my solution");
+
+    let output = dir.run(&["scaffold", "7", "--overwrite"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(
+        dir.read("src/bin/07.rs")
+            .starts_with("advent_of_code::solution!(7);")
+    );
+}
+
+#[test]
+fn adds_new_days_to_the_bench_list() {
+    let dir = TestDir::new("scaffold-bench");
+    std::fs::create_dir_all(dir.path.join("benches")).unwrap();
+    dir.write(
+        "benches/days.rs",
+        "benchmark_days! {\n    (3, day_03, \"../src/bin/03.rs\"),\n    (12, day_12, \"../src/bin/12.rs\"),\n}\n",
+    );
+
+    let output = dir.run(&["scaffold", "7"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        dir.read("benches/days.rs"),
+        "benchmark_days! {\n    (3, day_03, \"../src/bin/03.rs\"),\n    (7, day_07, \"../src/bin/07.rs\"),\n    (12, day_12, \"../src/bin/12.rs\"),\n}\n"
+    );
+
+    // scaffolding a day again does not duplicate its entry.
+    let output = dir.run(&["scaffold", "7", "--overwrite"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(dir.read("benches/days.rs").matches("day_07").count(), 1);
+}
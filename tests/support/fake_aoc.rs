@@ -0,0 +1,99 @@
+//! A fake aoc-cli for tests of the download and submit workflow, built by the integration tests.
+//! Point the template to it via `AOC_CLI`. It serves canned data from the directory in `FAKE_AOC_DIR`:
+//!  - `inputs/<day>.txt` and `puzzles/<day>.md` are written by `download` and printed by `read`.
+//!  - `answers/<day>-<part>.txt` holds the correct answer `submit` compares against.
+//!    Once answered correctly, further submissions of the part are rejected as already solved.
+//!
+//! Every invocation is appended to `invocations.log` in the same directory.
+use std::{env, fs, io::Write, path::PathBuf, process};
+
+fn fail(message: &str) -> ! {
+    eprintln!("fake-aoc: {message}");
+    process::exit(1);
+}
+
+fn main() {
+    let raw_args: Vec<String> = env::args().skip(1).collect();
+
+    if raw_args.first().is_some_and(|x| x == "-V") {
+        println!("aoc-cli 0.12.0 (fake)");
+        return;
+    }
+
+    let data_dir = PathBuf::from(env::var("FAKE_AOC_DIR").unwrap_or_else(|_| "fake-aoc".into()));
+
+    let mut log = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(data_dir.join("invocations.log"))
+        .unwrap_or_else(|e| fail(&format!("could not open invocation log: {e}")));
+    writeln!(log, "{}", raw_args.join(" ")).unwrap();
+
+    let mut args = pico_args::Arguments::from_vec(raw_args.iter().map(Into::into).collect());
+
+    let day: String = args
+        .value_from_str("--day")
+        .unwrap_or_else(|_| fail("missing --day"));
+    let _year: Option<u16> = args.opt_value_from_str("--year").unwrap();
+    let input_file: Option<String> = args.opt_value_from_str("--input-file").unwrap();
+    let puzzle_file: Option<String> = args.opt_value_from_str("--puzzle-file").unwrap();
+    let _ = args.contains("--overwrite");
+    let _ = args.contains("--description-only");
+
+    let canned = |folder: &str, file: String| {
+        fs::read_to_string(data_dir.join(folder).join(&file))
+            .unwrap_or_else(|_| fail(&format!("no canned {folder} for day {day}")))
+    };
+
+    let free: Vec<String> = args
+        .finish()
+        .into_iter()
+        .map(|x| x.to_string_lossy().into())
+        .collect();
+
+    match free.first().map(String::as_str) {
+        Some("download") => {
+            if let Some(path) = input_file {
+                fs::write(&path, canned("inputs", format!("{day}.txt"))).unwrap();
+                println!("Saved input to '{path}'");
+            }
+            if let Some(path) = puzzle_file {
+                fs::write(&path, canned("puzzles", format!("{day}.md"))).unwrap();
+                println!("Saved puzzle to '{path}'");
+            }
+        }
+        Some("read") => {
+            println!("{}", canned("puzzles", format!("{day}.md")));
+        }
+        Some("submit") => {
+            let (Some(part), Some(answer)) = (free.get(1), free.get(2)) else {
+                fail("usage: submit <part> <answer>");
+            };
+            let expected = canned("answers", format!("{day}-{part}.txt"));
+            let solved = data_dir
+                .join("answers")
+                .join(format!("{day}-{part}.solved"));
+
+            if solved.exists() {
+                println!(
+                    "You don't seem to be solving the right level.  Did you already complete it?"
+                );
+            } else if expected.trim() == answer {
+                fs::write(solved, answer).unwrap();
+                println!(
+                    "That's the right answer! You are one gold star closer to saving Christmas."
+                );
+            } else {
+                let hint = match (answer.parse::<i64>(), expected.trim().parse::<i64>()) {
+                    (Ok(a), Ok(e)) if a > e => " your answer is too high.",
+                    (Ok(a), Ok(e)) if a < e => " your answer is too low.",
+                    _ => "",
+                };
+                println!(
+                    "That's not the right answer;{hint} Please wait one minute before trying again."
+                );
+            }
+        }
+        _ => fail(&format!("unknown command: {free:?}")),
+    }
+}